@@ -3,7 +3,11 @@ use ems_geotechnical::{
     hydro::ProfilePorePressure,
     linspace,
     profile::Point,
-    soil::{layer::SoilLayer, model::Clay, profile::SoilProfile},
+    soil::{
+        layer::SoilLayer,
+        model::{Clay, Preconsolidation},
+        profile::SoilProfile,
+    },
 };
 use plotters::prelude::*;
 
@@ -13,7 +17,7 @@ fn main() {
             thickness: 1.0,
             soil_model: Box::new(Clay {
                 M: 7500.0,
-                over_consolidation_ratio: 10.0,
+                preconsolidation: Preconsolidation::Ocr(10.0),
                 ..Default::default()
             }),
         },
@@ -21,7 +25,7 @@ fn main() {
             thickness: 1.0,
             soil_model: Box::new(Clay {
                 M: 7500.0,
-                over_consolidation_ratio: 5.0,
+                preconsolidation: Preconsolidation::Ocr(5.0),
                 ..Default::default()
             }),
         },
@@ -29,7 +33,7 @@ fn main() {
             thickness: 1.0,
             soil_model: Box::new(Clay {
                 M: 7500.0,
-                over_consolidation_ratio: 4.0,
+                preconsolidation: Preconsolidation::Ocr(4.0),
                 ..Default::default()
             }),
         },
@@ -37,7 +41,7 @@ fn main() {
             thickness: 1.0,
             soil_model: Box::new(Clay {
                 M: 5000.0,
-                over_consolidation_ratio: 3.0,
+                preconsolidation: Preconsolidation::Ocr(3.0),
                 ..Default::default()
             }),
         },
@@ -45,7 +49,7 @@ fn main() {
             thickness: 1.0,
             soil_model: Box::new(Clay {
                 M: 5000.0,
-                over_consolidation_ratio: 1.5,
+                preconsolidation: Preconsolidation::Ocr(1.5),
                 ..Default::default()
             }),
         },
@@ -53,7 +57,7 @@ fn main() {
             thickness: 2.5,
             soil_model: Box::new(Clay {
                 M: 6000.0,
-                over_consolidation_ratio: 1.2,
+                preconsolidation: Preconsolidation::Ocr(1.2),
                 ..Default::default()
             }),
         },
@@ -61,7 +65,7 @@ fn main() {
             thickness: 2.5,
             soil_model: Box::new(Clay {
                 M: 7000.0,
-                over_consolidation_ratio: 1.2,
+                preconsolidation: Preconsolidation::Ocr(1.2),
                 ..Default::default()
             }),
         },
@@ -69,7 +73,7 @@ fn main() {
             thickness: 2.5,
             soil_model: Box::new(Clay {
                 M: 8000.0,
-                over_consolidation_ratio: 1.175,
+                preconsolidation: Preconsolidation::Ocr(1.175),
                 ..Default::default()
             }),
         },
@@ -77,7 +81,7 @@ fn main() {
             thickness: 2.5,
             soil_model: Box::new(Clay {
                 M: 10000.0,
-                over_consolidation_ratio: 1.15,
+                preconsolidation: Preconsolidation::Ocr(1.15),
                 ..Default::default()
             }),
         },
@@ -85,7 +89,7 @@ fn main() {
             thickness: 5.0,
             soil_model: Box::new(Clay {
                 M: 12500.0,
-                over_consolidation_ratio: 1.125,
+                preconsolidation: Preconsolidation::Ocr(1.125),
                 ..Default::default()
             }),
         },
@@ -93,7 +97,7 @@ fn main() {
             thickness: 5.0,
             soil_model: Box::new(Clay {
                 M: 15000.0,
-                over_consolidation_ratio: 1.1,
+                preconsolidation: Preconsolidation::Ocr(1.1),
                 ..Default::default()
             }),
         },
@@ -101,7 +105,7 @@ fn main() {
             thickness: 5.0,
             soil_model: Box::new(Clay {
                 M: 20000.0,
-                over_consolidation_ratio: 1.1,
+                preconsolidation: Preconsolidation::Ocr(1.1),
                 ..Default::default()
             }),
         },
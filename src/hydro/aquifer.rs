@@ -0,0 +1,27 @@
+/// Water bearing layer with its own piezometric level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aquifer {
+    /// Depth to the top of the aquifer
+    pub top: f64,
+    /// Depth to the bottom of the aquifer
+    pub bottom: f64,
+    /// Depth to the water level in a standpipe in the aquifer, negative for artesian heads
+    /// above the ground surface
+    pub piezometric_level: f64,
+}
+
+impl Aquifer {
+    #[must_use]
+    pub fn new(top: f64, bottom: f64, piezometric_level: f64) -> Self {
+        Self {
+            top,
+            bottom,
+            piezometric_level,
+        }
+    }
+    /// Pore pressure at `depth` for hydrostatic conditions within the aquifer
+    #[must_use]
+    pub fn pore_pressure(&self, depth: f64, unit_weight_water: f64) -> f64 {
+        (depth - self.piezometric_level) * unit_weight_water
+    }
+}
@@ -0,0 +1,120 @@
+use crate::hydro::{ProfilePorePressure, UNIT_WEIGHT_WATER};
+use crate::profile::{Point, Profile};
+
+/// Whether negative pore pressure adds to the effective stress
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Suction {
+    /// Effective stress is total stress less the pore pressure, also where it is negative
+    #[default]
+    Include,
+    /// Negative pore pressure is taken as zero in the effective stress
+    Ignore,
+}
+
+impl Suction {
+    /// Pore pressure to subtract from the total stress for the effective stress
+    #[must_use]
+    pub fn effective_pore_pressure(self, pore_pressure: f64) -> f64 {
+        match self {
+            Self::Include => pore_pressure,
+            Self::Ignore => pore_pressure.max(0.0),
+        }
+    }
+}
+
+/// Fully saturated capillary zone above the water table, where the pore pressure continues
+/// hydrostatically into suction up to the capillary rise. Above the capillary zone the pore
+/// pressure is taken as zero
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capillary {
+    /// Height of the capillary zone above the water table in metres
+    pub rise: f64,
+    /// Largest suction in kPa, as a positive number
+    pub suction_limit: Option<f64>,
+    pub unit_weight_water: f64,
+}
+
+impl Capillary {
+    #[must_use]
+    pub fn new(rise: f64) -> Self {
+        Self {
+            rise,
+            suction_limit: None,
+            unit_weight_water: UNIT_WEIGHT_WATER,
+        }
+    }
+    #[must_use]
+    pub fn with_suction_limit(mut self, suction_limit: f64) -> Self {
+        self.suction_limit = Some(suction_limit);
+        self
+    }
+    #[must_use]
+    pub fn with_unit_weight_water(mut self, unit_weight_water: f64) -> Self {
+        self.unit_weight_water = unit_weight_water;
+        self
+    }
+
+    /// Pore pressure at a height `height` above the water table within the capillary zone
+    #[must_use]
+    pub fn suction_pore_pressure(&self, height: f64) -> f64 {
+        let u = -self.unit_weight_water * height;
+        self.suction_limit.map_or(u, |limit| u.max(-limit))
+    }
+
+    /// Depth of the water table in `pore_pressure`, where the pore pressure first turns
+    /// positive going down. `None` if the pore pressure is never positive, or positive already
+    /// at the first point
+    #[must_use]
+    pub fn water_table(pore_pressure: &ProfilePorePressure) -> Option<f64> {
+        let points = pore_pressure.points();
+        let index = points.iter().position(|p| p.y > 0.0)?;
+        if index == 0 {
+            return None;
+        }
+        let (above, below) = (points[index - 1], points[index]);
+        Some(above.x - (below.x - above.x) * above.y / (below.y - above.y))
+    }
+
+    /// Replace the pore pressure above the water table of `pore_pressure` with the capillary
+    /// zone. Profiles without a water table below their first point are returned unchanged
+    #[must_use]
+    pub fn apply(&self, pore_pressure: &ProfilePorePressure) -> ProfilePorePressure {
+        let Some(water_table) = Self::water_table(pore_pressure) else {
+            return pore_pressure.clone();
+        };
+        let points = pore_pressure.points();
+        let top = points[0].x.min(0.0);
+        let zone_top = water_table - self.rise.max(0.0);
+
+        let mut above = Vec::new();
+        if zone_top > top {
+            above.push(Point::new(top, 0.0));
+            if self.rise > 0.0 {
+                above.push(Point::new(zone_top, 0.0));
+            }
+        }
+        if water_table > top && self.rise > 0.0 {
+            let start = zone_top.max(top);
+            above.push(Point::new(
+                start,
+                self.suction_pore_pressure(water_table - start),
+            ));
+            if let Some(limit) = self.suction_limit {
+                let limit_depth = water_table - limit / self.unit_weight_water;
+                if limit_depth > start && limit_depth < water_table {
+                    above.push(Point::new(limit_depth, -limit));
+                }
+            }
+        }
+        above.push(Point::new(water_table, 0.0));
+
+        ProfilePorePressure::new(
+            above
+                .into_iter()
+                .chain(points.iter().copied().filter(|p| p.x > water_table))
+                .collect(),
+        )
+        .with_extrapolation(pore_pressure.extrapolation())
+        .with_interpolation(pore_pressure.interpolation())
+    }
+}
@@ -0,0 +1,332 @@
+use std::fmt;
+
+use crate::hydro::{ProfilePorePressure, UNIT_WEIGHT_WATER};
+use crate::profile::{DepthProfile, Point, Profile};
+use crate::soil::profile::SoilProfile;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum ConsolidationError {
+    /// The soil layer at this index has neither a consolidation coefficient nor a permeability
+    MissingCoefficient { layer: usize },
+    /// The soil layer at this index has a coefficient or stiffness that is zero, negative or
+    /// not finite
+    InvalidCoefficient { layer: usize, value: f64 },
+    /// The number of consolidation coefficients does not match the number of soil layers
+    CoefficientCount { expected: usize, found: usize },
+    /// The soil profile has no layers above bedrock
+    NoLayers,
+}
+
+impl fmt::Display for ConsolidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCoefficient { layer } => write!(
+                f,
+                "soil layer {layer} has neither a consolidation coefficient nor a permeability"
+            ),
+            Self::InvalidCoefficient { layer, value } => {
+                write!(f, "soil layer {layer} has invalid coefficient {value}")
+            }
+            Self::CoefficientCount { expected, found } => write!(
+                f,
+                "expected {expected} consolidation coefficients, found {found}"
+            ),
+            Self::NoLayers => write!(f, "soil profile has no layers"),
+        }
+    }
+}
+
+impl std::error::Error for ConsolidationError {}
+
+/// Hydraulic condition at the top or bottom of the consolidating profile
+#[derive(Debug, Clone, PartialEq)]
+pub enum Boundary {
+    /// Impermeable, no flow across the boundary
+    Undrained,
+    /// Change in pore pressure in kPa against time in seconds, as (time, change) points.
+    /// Linear between the points and held before the first and after the last
+    Drained(Vec<Point>),
+}
+
+impl Boundary {
+    /// Drained boundary with a constant change in pore pressure
+    #[must_use]
+    pub fn drained(change: f64) -> Self {
+        Self::Drained(vec![Point::new(0.0, change)])
+    }
+
+    fn change(&self, time: f64) -> Option<f64> {
+        match self {
+            Self::Undrained => None,
+            Self::Drained(series) => Some(DepthProfile::new(series.clone()).eval(time)),
+        }
+    }
+}
+
+/// Finite volume element of the consolidating profile
+struct Element {
+    /// Depth to the bottom of the element
+    bottom: f64,
+    length: f64,
+    /// Hydraulic conductivity over unit weight of water, k / `γ_w`
+    conductance: f64,
+    /// Oedometer compressibility, 1 / M
+    storage: f64,
+}
+
+/// Transient 1D consolidation of the soil profile after a change in boundary pore pressure,
+/// starting from the pore pressure profile of the soil profile in steady state. Solved with
+/// implicit finite differences, using the oedometer modulus at the in-situ effective stress
+/// in the middle of each layer. Times are in seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consolidation {
+    pub top: Boundary,
+    pub bottom: Boundary,
+    /// Consolidation coefficient cv in m2/s for each soil layer, from the permeability of the
+    /// soil models if not given
+    pub coefficients: Option<Vec<f64>>,
+    pub unit_weight_water: f64,
+    pub element_size: f64,
+    /// Number of time steps up to the last requested time
+    pub time_steps: usize,
+}
+
+impl Consolidation {
+    #[must_use]
+    pub fn new(top: Boundary, bottom: Boundary) -> Self {
+        Self {
+            top,
+            bottom,
+            coefficients: None,
+            unit_weight_water: UNIT_WEIGHT_WATER,
+            element_size: 0.1,
+            time_steps: 1000,
+        }
+    }
+    #[must_use]
+    pub fn with_consolidation_coefficients(mut self, coefficients: Vec<f64>) -> Self {
+        self.coefficients = Some(coefficients);
+        self
+    }
+    #[must_use]
+    pub fn with_unit_weight_water(mut self, unit_weight_water: f64) -> Self {
+        self.unit_weight_water = unit_weight_water;
+        self
+    }
+    #[must_use]
+    pub fn with_element_size(mut self, element_size: f64) -> Self {
+        self.element_size = element_size;
+        self
+    }
+    #[must_use]
+    pub fn with_time_steps(mut self, time_steps: usize) -> Self {
+        self.time_steps = time_steps;
+        self
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn elements(&self, soil_profile: &SoilProfile) -> Result<Vec<Element>, ConsolidationError> {
+        let layers = soil_profile.layer_bounds();
+        if layers.is_empty() {
+            return Err(ConsolidationError::NoLayers);
+        }
+        if let Some(coefficients) = &self.coefficients {
+            if coefficients.len() != layers.len() {
+                return Err(ConsolidationError::CoefficientCount {
+                    expected: layers.len(),
+                    found: coefficients.len(),
+                });
+            }
+        }
+        let valid = |layer, value: f64| {
+            if value.is_finite() && value > 0.0 {
+                Ok(value)
+            } else {
+                Err(ConsolidationError::InvalidCoefficient { layer, value })
+            }
+        };
+
+        let mut elements = Vec::new();
+        for (layer, &(top, bottom, soil_layer)) in layers.iter().enumerate() {
+            let middle = f64::midpoint(top, bottom);
+            let p0 = soil_profile.in_situ_effective_stress(middle).unwrap_or(0.0);
+            let pc = soil_profile.pc(middle).unwrap_or(p0);
+            let modulus = valid(
+                layer,
+                soil_layer.soil_model.elastic_modulus_with_pc(p0, pc, 0.0),
+            )?;
+            let conductance = match (&self.coefficients, soil_layer.permeability()) {
+                (Some(coefficients), _) => valid(layer, coefficients[layer])? / modulus,
+                (None, Some(permeability)) => valid(layer, permeability)? / self.unit_weight_water,
+                (None, None) => return Err(ConsolidationError::MissingCoefficient { layer }),
+            };
+
+            let n = ((bottom - top) / self.element_size).ceil().max(1.0) as usize;
+            elements.extend((1..=n).map(|i| Element {
+                bottom: top + (bottom - top) * i as f64 / n as f64,
+                length: (bottom - top) / n as f64,
+                conductance,
+                storage: 1.0 / modulus,
+            }));
+        }
+
+        Ok(elements)
+    }
+
+    /// Change in pore pressure at the nodes at each of the requested times
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn march(&self, elements: &[Element], times: &[f64]) -> Vec<Vec<f64>> {
+        let n = elements.len() + 1;
+        let mut storage = vec![0.0; n];
+        for (i, element) in elements.iter().enumerate() {
+            storage[i] += element.length * element.storage / 2.0;
+            storage[i + 1] += element.length * element.storage / 2.0;
+        }
+        let stiffness = elements
+            .iter()
+            .map(|e| e.conductance / e.length)
+            .collect::<Vec<f64>>();
+
+        let t_max = times.iter().copied().fold(0.0, f64::max);
+        let max_step = t_max / self.time_steps.max(1) as f64;
+        let mut change = vec![0.0; n];
+        let mut time = 0.0;
+        let set_boundaries = |change: &mut Vec<f64>, time: f64| {
+            if let Some(value) = self.top.change(time) {
+                change[0] = value;
+            }
+            if let Some(value) = self.bottom.change(time) {
+                change[n - 1] = value;
+            }
+        };
+
+        // March through the times in ascending order and return them in the requested order
+        let mut order = (0..times.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| times[a].total_cmp(&times[b]));
+        let mut snapshots = vec![Vec::new(); times.len()];
+        for index in order {
+            let target = times[index];
+            if target > time {
+                let steps = ((target - time) / max_step).ceil().max(1.0);
+                let dt = (target - time) / steps;
+                for _ in 0..steps as usize {
+                    time += dt;
+                    change = self.step(&storage, &stiffness, &change, dt, time);
+                }
+                time = target;
+            }
+            let mut snapshot = change.clone();
+            set_boundaries(&mut snapshot, target);
+            snapshots[index] = snapshot;
+        }
+
+        snapshots
+    }
+
+    /// One backward Euler step, solving the tridiagonal system with the Thomas algorithm
+    fn step(
+        &self,
+        storage: &[f64],
+        stiffness: &[f64],
+        change: &[f64],
+        dt: f64,
+        time: f64,
+    ) -> Vec<f64> {
+        let n = change.len();
+        let mut lower = vec![0.0; n];
+        let mut diagonal = vec![0.0; n];
+        let mut upper = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+
+        for i in 0..n {
+            diagonal[i] = storage[i] / dt;
+            rhs[i] = storage[i] / dt * change[i];
+            if i > 0 {
+                lower[i] = -stiffness[i - 1];
+                diagonal[i] += stiffness[i - 1];
+            }
+            if i < n - 1 {
+                upper[i] = -stiffness[i];
+                diagonal[i] += stiffness[i];
+            }
+        }
+        for (i, boundary) in [(0, &self.top), (n - 1, &self.bottom)] {
+            if let Some(value) = boundary.change(time) {
+                lower[i] = 0.0;
+                upper[i] = 0.0;
+                diagonal[i] = 1.0;
+                rhs[i] = value;
+            }
+        }
+
+        for i in 1..n {
+            let factor = lower[i] / diagonal[i - 1];
+            diagonal[i] -= factor * upper[i - 1];
+            rhs[i] -= factor * rhs[i - 1];
+        }
+        let mut res = vec![0.0; n];
+        res[n - 1] = rhs[n - 1] / diagonal[n - 1];
+        for i in (0..n - 1).rev() {
+            res[i] = (rhs[i] - upper[i] * res[i + 1]) / diagonal[i];
+        }
+        res
+    }
+
+    /// Pore pressure profiles at the requested times, in the order given. Times at or before
+    /// zero give the initial pore pressure with the boundary changes at that time
+    /// # Errors
+    /// Returns a `ConsolidationError` if a layer lacks a valid consolidation coefficient or
+    /// permeability, or the soil profile has no layers
+    pub fn solve(
+        &self,
+        soil_profile: &SoilProfile,
+        times: &[f64],
+    ) -> Result<Vec<ProfilePorePressure>, ConsolidationError> {
+        let elements = self.elements(soil_profile)?;
+        let depths = std::iter::once(0.0)
+            .chain(elements.iter().map(|element| element.bottom))
+            .collect::<Vec<f64>>();
+        let initial = soil_profile.pore_pressure_profile();
+
+        Ok(self
+            .march(&elements, times)
+            .iter()
+            .map(|change| {
+                let change = ProfilePorePressure::new(
+                    depths
+                        .iter()
+                        .zip(change)
+                        .map(|(&z, &du)| Point::new(z, du))
+                        .collect(),
+                );
+                initial.zip_with(&change, |u, du| u + du)
+            })
+            .collect())
+    }
+
+    /// Settlement against time as (time, settlement) points, see
+    /// [`SoilProfile::compute_settlement`]
+    /// # Errors
+    /// See [`Consolidation::solve`]
+    pub fn settlement_curve(
+        &self,
+        soil_profile: &SoilProfile,
+        times: &[f64],
+    ) -> Result<Vec<Point>, ConsolidationError> {
+        Ok(self
+            .solve(soil_profile, times)?
+            .iter()
+            .zip(times)
+            .map(|(profile, &t)| Point::new(t, soil_profile.compute_settlement(profile)))
+            .collect())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::hydro::Capillary;
+use crate::linspace;
+use crate::profile::{DepthProfile, Point};
+
+/// Distribution of a pore pressure change over depth
+#[derive(Debug, Clone)]
+pub enum DrawdownShape {
+    /// Full change throughout the profile
+    Uniform,
+    /// Full change at the bottom aquifer, reducing linearly to nothing at the influence height
+    /// above it
+    Linear,
+    /// Full change at the bottom aquifer, decaying exponentially upwards with the influence
+    /// height as decay length
+    Exponential,
+    /// Full change in a top aquifer down to `aquifer_bottom`, reducing linearly to nothing at
+    /// the influence height below it
+    TopAquifer { aquifer_bottom: f64 },
+    /// Fraction of the full change given at each depth
+    Custom(DepthProfile),
+}
+
+/// Description of a change in pore pressure, such as from dewatering
+#[derive(Debug, Clone)]
+pub struct Drawdown {
+    /// Full change in pore pressure in kPa, negative for a reduction
+    pub change: f64,
+    pub shape: DrawdownShape,
+    pub influence_height: f64,
+    /// Whether the resulting pore pressure is kept from going negative
+    pub clamp_at_zero: bool,
+    /// Capillary zone above the resulting water table, replacing the clamping
+    pub capillary: Option<Capillary>,
+    /// Depth of the bottom aquifer, the last point of the pore pressure profile if not given
+    pub bottom: Option<f64>,
+    /// Number of points used for shapes that are not piecewise linear
+    pub samples: usize,
+}
+
+impl Default for Drawdown {
+    fn default() -> Self {
+        Self {
+            change: 0.0,
+            shape: DrawdownShape::Linear,
+            influence_height: 10.0,
+            clamp_at_zero: true,
+            capillary: None,
+            bottom: None,
+            samples: 100,
+        }
+    }
+}
+
+impl Drawdown {
+    #[must_use]
+    pub fn new(change: f64) -> Self {
+        Self {
+            change,
+            ..Default::default()
+        }
+    }
+    #[must_use]
+    pub fn with_shape(mut self, shape: DrawdownShape) -> Self {
+        self.shape = shape;
+        self
+    }
+    #[must_use]
+    pub fn with_influence_height(mut self, influence_height: f64) -> Self {
+        self.influence_height = influence_height;
+        self
+    }
+    #[must_use]
+    pub fn with_clamp_at_zero(mut self, clamp_at_zero: bool) -> Self {
+        self.clamp_at_zero = clamp_at_zero;
+        self
+    }
+    #[must_use]
+    pub fn with_capillary(mut self, capillary: Capillary) -> Self {
+        self.capillary = Some(capillary);
+        self
+    }
+    #[must_use]
+    pub fn with_bottom(mut self, bottom: f64) -> Self {
+        self.bottom = Some(bottom);
+        self
+    }
+    /// Number of points for shapes that are not piecewise linear, at least two
+    #[must_use]
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(2);
+        self
+    }
+
+    /// Change in pore pressure from `top` down to the bottom aquifer at `bottom`
+    #[must_use]
+    pub fn change_profile(&self, top: f64, bottom: f64) -> DepthProfile {
+        let h = self.influence_height;
+        let change = self.change;
+
+        match &self.shape {
+            DrawdownShape::Uniform => DepthProfile::new(vec![Point::new(bottom, change)]),
+            DrawdownShape::Linear => DepthProfile::new(vec![
+                Point::new(bottom - h, 0.0),
+                Point::new(bottom, change),
+            ]),
+            DrawdownShape::Exponential => DepthProfile::new(
+                linspace(top, bottom, self.samples.max(2))
+                    .iter()
+                    .map(|&z| Point::new(z, change * (-(bottom - z) / h).exp()))
+                    .collect(),
+            ),
+            DrawdownShape::TopAquifer { aquifer_bottom } => DepthProfile::new(vec![
+                Point::new(*aquifer_bottom, change),
+                Point::new(aquifer_bottom + h, 0.0),
+            ]),
+            DrawdownShape::Custom(factor) => factor * change,
+        }
+        .with_name("Pore pressure change")
+        .with_unit("kPa")
+    }
+}
@@ -0,0 +1,118 @@
+use crate::hydro::ProfilePorePressure;
+use crate::profile::impl_profile_ops;
+use crate::profile::{Datum, Extrapolation, Interpolation, Point, Profile};
+
+/// Piezometric head against depth, with the head given as the elevation of the water level in
+/// metres relative to the same reference as the ground surface of a [`Datum`]
+#[derive(Debug, Clone)]
+pub struct HeadProfile {
+    points: Vec<Point>,
+    extrapolation: Extrapolation,
+    interpolation: Interpolation,
+}
+
+impl HeadProfile {
+    #[must_use]
+    pub fn new(points: Vec<Point>) -> Self {
+        let mut copy = points;
+        copy.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self {
+            points: copy,
+            extrapolation: Extrapolation::default(),
+            interpolation: Interpolation::default(),
+        }
+    }
+    #[must_use]
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    /// Heads from the pore pressure in `pore_pressure`, as h = z + u / `γ_w`. Where the pore
+    /// pressure is zero above the water table the head is the elevation itself. Linear
+    /// extrapolation carries over, other policies refuse to evaluate outside the points
+    #[must_use]
+    pub fn from_pore_pressure(
+        pore_pressure: &ProfilePorePressure,
+        datum: Datum,
+        unit_weight_water: f64,
+    ) -> Self {
+        Self::new(
+            pore_pressure
+                .points()
+                .iter()
+                .map(|p| Point::new(p.x, datum.elevation(p.x) + p.y / unit_weight_water))
+                .collect(),
+        )
+        .with_extrapolation(convert_extrapolation(pore_pressure.extrapolation()))
+        .with_interpolation(pore_pressure.interpolation())
+    }
+    /// Pore pressure from the heads, as u = `γ_w` (h - z). Linear extrapolation carries over,
+    /// other policies refuse to evaluate outside the points
+    #[must_use]
+    pub fn to_pore_pressure(&self, datum: Datum, unit_weight_water: f64) -> ProfilePorePressure {
+        ProfilePorePressure::new(
+            self.points
+                .iter()
+                .map(|p| Point::new(p.x, unit_weight_water * (p.y - datum.elevation(p.x))))
+                .collect(),
+        )
+        .with_extrapolation(convert_extrapolation(self.extrapolation))
+        .with_interpolation(self.interpolation)
+    }
+    /// Mean vertical hydraulic gradient between the depths `top` and `bottom`, the loss of
+    /// head over the distance. Positive for downward flow and negative for upward flow
+    #[must_use]
+    pub fn vertical_gradient(&self, top: f64, bottom: f64) -> f64 {
+        (self.eval(top) - self.eval(bottom)) / (bottom - top)
+    }
+    /// Vertical hydraulic gradient at `depth`, positive for downward flow
+    #[must_use]
+    pub fn vertical_gradient_at(&self, depth: f64) -> f64 {
+        -self.gradient(depth)
+    }
+}
+
+/// Extrapolation between pore pressure and head, which differ by the elevation. A linear
+/// continuation stays linear, while a constant or clamped value in one is not constant in the
+/// other and can not be expressed, so evaluation outside the points is refused instead
+fn convert_extrapolation(extrapolation: Extrapolation) -> Extrapolation {
+    match extrapolation {
+        Extrapolation::Linear => Extrapolation::Linear,
+        Extrapolation::Clamp | Extrapolation::Constant(_) | Extrapolation::Error => {
+            Extrapolation::Error
+        }
+    }
+}
+
+impl Profile for HeadProfile {
+    fn new(points: Vec<Point>) -> Self {
+        HeadProfile::new(points)
+    }
+
+    fn points(&self) -> &Vec<Point> {
+        &self.points
+    }
+
+    fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
+    }
+
+    fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    fn with_extrapolation(self, extrapolation: Extrapolation) -> Self {
+        HeadProfile::with_extrapolation(self, extrapolation)
+    }
+
+    fn with_interpolation(self, interpolation: Interpolation) -> Self {
+        HeadProfile::with_interpolation(self, interpolation)
+    }
+}
+
+impl_profile_ops!(HeadProfile);
@@ -1,5 +1,30 @@
+mod aquifer;
+mod capillary;
+mod consolidation;
+mod drawdown;
+mod head_profile;
+mod piezometer;
 mod porepressure_profile;
+mod section;
+mod seepage;
+mod uplift;
+mod wells;
+pub use aquifer::Aquifer;
+pub use capillary::{Capillary, Suction};
+pub use consolidation::{Boundary, Consolidation, ConsolidationError};
+pub use drawdown::{Drawdown, DrawdownShape};
+pub use head_profile::HeadProfile;
+pub use piezometer::{
+    Envelope, FilterLevel, Piezometer, PiezometerError, PiezometerSet, Timestamp,
+};
 pub use porepressure_profile::ProfilePorePressure;
+pub use section::{Edge, FixedHead, SectionSeepage, SeepageField, Wall};
+pub use seepage::{hydraulic_resistance, SeepageError, SteadySeepage};
+pub use uplift::{Uplift, UpliftError, UpliftInterface, UpliftReport};
+pub use wells::{leaky_well_function, well_function, RadialFlow, Well, WellField};
+
+/// Unit weight of water in kN/m3
+pub const UNIT_WEIGHT_WATER: f64 = 9.81;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,410 @@
+use std::fmt;
+
+use crate::hydro::ProfilePorePressure;
+use crate::profile::{
+    Datum, DepthProfile, Extrapolation, Interpolation, Point, Profile, ProfileError,
+};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum PiezometerError {
+    /// The first line of the CSV data lacks one of the required columns
+    MissingColumn { column: &'static str },
+    /// A field on this line of the CSV data could not be parsed
+    InvalidField { line: usize, column: &'static str },
+    /// The text is not a date on the form YYYY-MM-DD, optionally followed by HH:MM or HH:MM:SS
+    InvalidTimestamp { text: String },
+    /// No piezometer has a reading covering the requested time or period
+    NoReadings,
+    /// The readings do not form a valid pore pressure profile
+    Profile(ProfileError),
+}
+
+impl fmt::Display for PiezometerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColumn { column } => write!(f, "missing column {column}"),
+            Self::InvalidField { line, column } => {
+                write!(f, "invalid {column} on line {line}")
+            }
+            Self::InvalidTimestamp { text } => write!(f, "invalid timestamp {text}"),
+            Self::NoReadings => write!(f, "no piezometer readings for the requested time"),
+            Self::Profile(error) => write!(f, "invalid pore pressure profile: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PiezometerError {}
+
+impl From<ProfileError> for PiezometerError {
+    fn from(error: ProfileError) -> Self {
+        Self::Profile(error)
+    }
+}
+
+/// Point in time as seconds since 1970-01-01 00:00, without time zones
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Timestamp(pub f64);
+
+impl Timestamp {
+    /// Midnight at the start of the given date in the proleptic Gregorian calendar
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_date(year: i32, month: u32, day: u32) -> Self {
+        // Days from civil, H. Hinnant
+        let year = i64::from(year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        Self(days as f64 * SECONDS_PER_DAY)
+    }
+    #[must_use]
+    pub fn with_time(self, hour: u32, minute: u32, second: u32) -> Self {
+        Self(self.0 + f64::from(hour * 3600 + minute * 60 + second))
+    }
+    /// Parse a date on the form YYYY-MM-DD, optionally followed by a space or `T` and a time
+    /// on the form HH:MM or HH:MM:SS
+    /// # Errors
+    /// Returns `PiezometerError::InvalidTimestamp` if the text is not on this form or gives an
+    /// invalid date or time
+    pub fn parse(text: &str) -> Result<Self, PiezometerError> {
+        let invalid = || PiezometerError::InvalidTimestamp {
+            text: text.to_owned(),
+        };
+        let text = text.trim();
+        let (date, time) = match text.split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+
+        let mut date_parts = date.splitn(3, '-');
+        let mut next = || date_parts.next().and_then(|part| part.parse::<u32>().ok());
+        let (year, month, day) = (next(), next(), next());
+        let (Some(year), Some(month), Some(day)) = (year, month, day) else {
+            return Err(invalid());
+        };
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+        let timestamp = Self::from_date(i32::try_from(year).map_err(|_| invalid())?, month, day);
+
+        let Some(time) = time else {
+            return Ok(timestamp);
+        };
+        let parts = time
+            .split(':')
+            .map(|part| part.parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        match parts[..] {
+            [hour, minute] if hour < 24 && minute < 60 => Ok(timestamp.with_time(hour, minute, 0)),
+            [hour, minute, second] if hour < 24 && minute < 60 && second < 60 => {
+                Ok(timestamp.with_time(hour, minute, second))
+            }
+            _ => Err(invalid()),
+        }
+    }
+    /// Calendar date as (year, month, day)
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn date(&self) -> (i32, u32, u32) {
+        // Civil from days, H. Hinnant
+        let days = (self.0 / SECONDS_PER_DAY).floor() as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        (year as i32, month as u32, day as u32)
+    }
+    #[must_use]
+    pub fn month(&self) -> u32 {
+        self.date().1
+    }
+    /// Seconds elapsed since `earlier`
+    #[must_use]
+    pub fn seconds_since(&self, earlier: Timestamp) -> f64 {
+        self.0 - earlier.0
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Position of the piezometer filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterLevel {
+    Depth(f64),
+    Elevation(f64),
+}
+
+impl FilterLevel {
+    #[must_use]
+    pub fn depth(&self, datum: Datum) -> f64 {
+        match *self {
+            Self::Depth(depth) => depth,
+            Self::Elevation(elevation) => datum.depth(elevation),
+        }
+    }
+}
+
+/// Pore pressure readings in kPa from one piezometer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Piezometer {
+    pub name: String,
+    pub level: FilterLevel,
+    readings: Vec<(Timestamp, f64)>,
+}
+
+impl Piezometer {
+    #[must_use]
+    pub fn new(name: &str, level: FilterLevel) -> Self {
+        Self {
+            name: name.to_owned(),
+            level,
+            readings: Vec::new(),
+        }
+    }
+    #[must_use]
+    pub fn with_readings(mut self, readings: Vec<(Timestamp, f64)>) -> Self {
+        for (time, pore_pressure) in readings {
+            self.add_reading(time, pore_pressure);
+        }
+        self
+    }
+    /// Add a reading, keeping the readings in time order. A reading at the same time as an
+    /// existing one replaces it
+    pub fn add_reading(&mut self, time: Timestamp, pore_pressure: f64) {
+        let index = self.readings.partition_point(|(t, _)| *t < time);
+        match self.readings.get_mut(index) {
+            Some(reading) if reading.0 == time => reading.1 = pore_pressure,
+            _ => self.readings.insert(index, (time, pore_pressure)),
+        }
+    }
+    #[must_use]
+    pub fn readings(&self) -> &[(Timestamp, f64)] {
+        &self.readings
+    }
+    /// Pore pressure at `time`, linear between readings. `None` outside the period covered by
+    /// the readings
+    #[must_use]
+    pub fn pore_pressure_at(&self, time: Timestamp) -> Option<f64> {
+        let series = self
+            .readings
+            .iter()
+            .map(|(t, u)| Point::new(t.0, *u))
+            .collect();
+        DepthProfile::new(series)
+            .with_extrapolation(Extrapolation::Error)
+            .try_eval(time.0)
+            .ok()
+    }
+    /// Smallest and largest reading in the months of the year given by `months`, from 1 for
+    /// January to 12 for December
+    #[must_use]
+    pub fn range_in_months(&self, months: &[u32]) -> Option<(f64, f64)> {
+        self.readings
+            .iter()
+            .filter(|(t, _)| months.contains(&t.month()))
+            .fold(None, |acc, &(_, u)| match acc {
+                None => Some((u, u)),
+                Some((min, max)) => Some((f64::min(min, u), f64::max(max, u))),
+            })
+    }
+}
+
+/// Minimum and maximum pore pressure profiles over a period
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    pub min: ProfilePorePressure,
+    pub max: ProfilePorePressure,
+}
+
+/// Piezometers in one borehole or location, giving pore pressure profiles in time
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PiezometerSet {
+    pub piezometers: Vec<Piezometer>,
+    pub datum: Datum,
+    /// Scheme used between the filter depths of the profiles
+    pub interpolation: Interpolation,
+    /// Behaviour of the profiles above the shallowest and below the deepest filter
+    pub extrapolation: Extrapolation,
+}
+
+impl PiezometerSet {
+    #[must_use]
+    pub fn new(piezometers: Vec<Piezometer>) -> Self {
+        Self {
+            piezometers,
+            ..Default::default()
+        }
+    }
+    #[must_use]
+    pub fn with_datum(mut self, datum: Datum) -> Self {
+        self.datum = datum;
+        self
+    }
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    #[must_use]
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Read piezometers from CSV data with a header line naming the columns `piezometer`,
+    /// `depth` or `elevation`, `time` and `pore_pressure`, in any order, and one reading per
+    /// line. Readings from the same piezometer name are collected in one piezometer
+    /// # Errors
+    /// Returns a `PiezometerError` if a column is missing or a field can not be parsed
+    pub fn from_csv(csv: &str) -> Result<Self, PiezometerError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let header = lines
+            .next()
+            .map(|(_, line)| split_csv(line))
+            .unwrap_or_default();
+        let column = |name: &'static str| {
+            header
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(name))
+                .ok_or(PiezometerError::MissingColumn { column: name })
+        };
+        let name_column = column("piezometer")?;
+        let (level_column, level_name) = match (column("depth"), column("elevation")) {
+            (Ok(index), _) => (index, "depth"),
+            (_, Ok(index)) => (index, "elevation"),
+            (Err(_), Err(error)) => return Err(error),
+        };
+        let time_column = column("time")?;
+        let pore_pressure_column = column("pore_pressure")?;
+
+        let mut piezometers: Vec<Piezometer> = Vec::new();
+        for (index, line) in lines {
+            let line_number = index + 1;
+            let fields = split_csv(line);
+            let field = |column: usize, name: &'static str| {
+                fields
+                    .get(column)
+                    .copied()
+                    .ok_or(PiezometerError::InvalidField {
+                        line: line_number,
+                        column: name,
+                    })
+            };
+            let number = |column: usize, name: &'static str| {
+                field(column, name)?
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|value| value.is_finite())
+                    .ok_or(PiezometerError::InvalidField {
+                        line: line_number,
+                        column: name,
+                    })
+            };
+
+            let name = field(name_column, "piezometer")?;
+            let level = number(level_column, level_name)?;
+            let level = if level_name == "depth" {
+                FilterLevel::Depth(level)
+            } else {
+                FilterLevel::Elevation(level)
+            };
+            let time = Timestamp::parse(field(time_column, "time")?).map_err(|_| {
+                PiezometerError::InvalidField {
+                    line: line_number,
+                    column: "time",
+                }
+            })?;
+            let pore_pressure = number(pore_pressure_column, "pore_pressure")?;
+
+            match piezometers.iter_mut().find(|p| p.name == name) {
+                Some(piezometer) => piezometer.add_reading(time, pore_pressure),
+                None => piezometers
+                    .push(Piezometer::new(name, level).with_readings(vec![(time, pore_pressure)])),
+            }
+        }
+        Ok(Self::new(piezometers))
+    }
+
+    fn profile(&self, points: Vec<Point>) -> Result<ProfilePorePressure, PiezometerError> {
+        if points.is_empty() {
+            return Err(PiezometerError::NoReadings);
+        }
+        Ok(ProfilePorePressure::try_new(points)?
+            .with_interpolation(self.interpolation)
+            .with_extrapolation(self.extrapolation))
+    }
+
+    /// Pore pressure profile at `time`, interpolated in time for each piezometer and in depth
+    /// between them. Piezometers without readings on both sides of `time` are left out
+    /// # Errors
+    /// Returns `PiezometerError::NoReadings` if no piezometer covers `time`, or
+    /// `PiezometerError::Profile` if two piezometers at the same depth disagree
+    pub fn profile_at(&self, time: Timestamp) -> Result<ProfilePorePressure, PiezometerError> {
+        self.profile(
+            self.piezometers
+                .iter()
+                .filter_map(|p| {
+                    p.pore_pressure_at(time)
+                        .map(|u| Point::new(p.level.depth(self.datum), u))
+                })
+                .collect(),
+        )
+    }
+
+    /// Profiles of the smallest and largest reading of each piezometer in the given months of
+    /// the year, from 1 for January to 12 for December, over all years of the record. Use all
+    /// twelve months for the full range of the record
+    /// # Errors
+    /// See [`PiezometerSet::profile_at`]
+    pub fn seasonal_envelope(&self, months: &[u32]) -> Result<Envelope, PiezometerError> {
+        let (min, max) = self
+            .piezometers
+            .iter()
+            .filter_map(|p| {
+                let depth = p.level.depth(self.datum);
+                p.range_in_months(months)
+                    .map(|(min, max)| (Point::new(depth, min), Point::new(depth, max)))
+            })
+            .unzip();
+
+        Ok(Envelope {
+            min: self.profile(min)?,
+            max: self.profile(max)?,
+        })
+    }
+}
+
+fn split_csv(line: &str) -> Vec<&str> {
+    line.split([',', ';']).map(str::trim).collect()
+}
@@ -1,18 +1,25 @@
 use cgmath::Vector2;
 
-use crate::linspace;
+use crate::hydro::{Aquifer, Drawdown};
+use crate::profile::impl_profile_ops;
+use crate::profile::Extrapolation;
+use crate::profile::Interpolation;
 use crate::profile::Point;
 use crate::profile::Profile;
 
 #[derive(Debug, Clone)]
 pub struct ProfilePorePressure {
     points: Vec<Vector2<f64>>,
+    extrapolation: Extrapolation,
+    interpolation: Interpolation,
 }
 
 impl Default for ProfilePorePressure {
     fn default() -> Self {
         Self {
             points: vec![Point::new(0.0, 0.0), Point::new(100.0, 1000.0)],
+            extrapolation: Extrapolation::default(),
+            interpolation: Interpolation::default(),
         }
     }
 }
@@ -22,33 +29,121 @@ impl ProfilePorePressure {
     pub fn new(points: Vec<Point>) -> Self {
         let mut copy = points;
         copy.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
-        Self { points: copy }
+        Self {
+            points: copy,
+            extrapolation: Extrapolation::default(),
+            interpolation: Interpolation::default(),
+        }
+    }
+    #[must_use]
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    /// Hydrostatic pore pressure below a groundwater table down to `bottom`, continued
+    /// linearly below it. A negative `water_table` lies above the ground surface
+    #[must_use]
+    pub fn hydrostatic(water_table: f64, bottom: f64, unit_weight_water: f64) -> Self {
+        Self::aquifers(&[Aquifer::new(0.0, bottom, water_table)], unit_weight_water)
     }
+    /// Perched groundwater resting on a low permeability layer, with a dry zone between the
+    /// base of that layer and the main groundwater table. The pore pressure falls linearly to
+    /// zero through the perching layer
+    #[must_use]
+    pub fn perched(
+        perched_water_table: f64,
+        perching_layer: (f64, f64),
+        water_table: f64,
+        bottom: f64,
+        unit_weight_water: f64,
+    ) -> Self {
+        Self::aquifers(
+            &[
+                Aquifer::new(0.0, perching_layer.0, perched_water_table),
+                Aquifer::new(perching_layer.1, bottom, water_table),
+            ],
+            unit_weight_water,
+        )
+    }
+    /// Stacked aquifers, each hydrostatic about its own piezometric level. The pore pressure
+    /// varies linearly through the aquitards between them and from zero at the ground surface,
+    /// and aquifers sharing a boundary give a jump in pore pressure. Above the water table in
+    /// an unconfined aquifer the pore pressure is zero
+    #[must_use]
+    pub fn aquifers(aquifers: &[Aquifer], unit_weight_water: f64) -> Self {
+        let mut sorted = aquifers.to_vec();
+        sorted.sort_by(|a, b| a.top.total_cmp(&b.top));
+        let mut points = Vec::new();
+
+        if sorted.first().is_some_and(|aquifer| aquifer.top > 0.0) {
+            points.push(Point::new(0.0, 0.0));
+        }
+        for (index, aquifer) in sorted.iter().enumerate() {
+            let u = |z| aquifer.pore_pressure(z, unit_weight_water);
+            let level = aquifer.piezometric_level;
+
+            if level >= aquifer.bottom {
+                points.push(Point::new(aquifer.top, 0.0));
+                points.push(Point::new(aquifer.bottom, 0.0));
+                // Water table below the deepest aquifer, continued hydrostatically below it
+                if index + 1 == sorted.len() {
+                    if level > aquifer.bottom {
+                        points.push(Point::new(level, 0.0));
+                    }
+                    points.push(Point::new(level + 1.0, u(level + 1.0)));
+                }
+            } else if level > aquifer.top {
+                points.push(Point::new(aquifer.top, 0.0));
+                points.push(Point::new(level, 0.0));
+                points.push(Point::new(aquifer.bottom, u(aquifer.bottom)));
+            } else {
+                points.push(Point::new(aquifer.top, u(aquifer.top)));
+                points.push(Point::new(aquifer.bottom, u(aquifer.bottom)));
+            }
+        }
+
+        Self::new(points).with_extrapolation(Extrapolation::Linear)
+    }
+    /// Pore pressure after a linear drawdown of `d_u_0` at the bottom, see [`Drawdown`]
     /// # Panics
     /// Will panic if pointslist is empty
     #[must_use]
     pub fn drawdown_profile(origin_profile: &Self, d_u_0: f64) -> Self {
-        const INFLUENCE_DEPTH: f64 = 10.0;
-        const n: usize = 100;
-        let total_depth = origin_profile.points.last().unwrap().x;
-
-        let new_points = linspace(0.0, total_depth, n)
-            .iter()
-            .map(|&z| {
-                let u_0 = origin_profile.eval(z);
-                if z >= total_depth - INFLUENCE_DEPTH {
-                    let elapsed_depth = total_depth - z;
-                    let d_u = d_u_0 * (INFLUENCE_DEPTH - elapsed_depth) / INFLUENCE_DEPTH;
-                    let u = u_0 + d_u;
-
-                    Point::new(z, u.max(0.0))
-                } else {
-                    Point::new(z, u_0)
-                }
-            })
-            .collect::<Vec<Point>>();
+        origin_profile.apply_drawdown(&Drawdown::new(d_u_0))
+    }
+    /// Pore pressure after the change described by `drawdown`, from the ground surface down to
+    /// the bottom aquifer
+    /// # Panics
+    /// Will panic if pointslist is empty
+    #[must_use]
+    pub fn apply_drawdown(&self, drawdown: &Drawdown) -> Self {
+        let top = self.points.first().unwrap().x.min(0.0);
+        let bottom = drawdown
+            .bottom
+            .unwrap_or_else(|| self.points.last().unwrap().x);
+        let change = drawdown.change_profile(top, bottom);
+
+        let mut res = self.zip_with(&change, |u, d_u| u + d_u);
+        if let Some(capillary) = drawdown.capillary {
+            res = capillary.apply(&res);
+        } else if drawdown.clamp_at_zero {
+            let zeros = res.zeros();
+            res = if zeros.is_empty() {
+                res.map(|u| u.max(0.0))
+            } else {
+                let zeros = zeros.iter().map(|&z| Point::new(z, 0.0)).collect();
+                res.zip_with(&Self::new(zeros), |u, _| u.max(0.0))
+            };
+        }
+        res.points.retain(|p| p.x >= top);
 
-        ProfilePorePressure::new(new_points)
+        res.with_extrapolation(self.extrapolation)
+            .with_interpolation(self.interpolation)
     }
 }
 
@@ -60,64 +155,22 @@ impl Profile for ProfilePorePressure {
     fn points(&self) -> &Vec<Point> {
         &self.points
     }
-}
 
-impl std::ops::Sub<Self> for ProfilePorePressure {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self::new(
-            self.xs()
-                .iter()
-                .chain(rhs.xs().iter())
-                .copied()
-                .collect::<Vec<f64>>()
-                .unique(0.1)
-                .iter()
-                .map(|&x| Point::new(x, self.eval(x) - rhs.eval(x)))
-                .collect::<Vec<Point>>(),
-        )
+    fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
     }
-}
-
-trait UniqueEnough {
-    fn unique(&self, interval: f64) -> Self;
-}
 
-impl UniqueEnough for Vec<f64> {
-    fn unique(&self, interval: f64) -> Self {
-        let mut copy = self.clone();
-        copy.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-        if let 0..=2 = copy.len() {
-            copy
-        } else {
-            let mut res = vec![*copy.first().unwrap()];
-
-            copy[1..copy.len() - 1].iter().for_each(|element| {
-                if (element - res.last().unwrap()) >= (interval.abs() * 0.999) {
-                    res.push(*element);
-                }
-            });
-
-            res.push(*copy.last().unwrap());
-
-            res
-        }
+    fn interpolation(&self) -> Interpolation {
+        self.interpolation
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn unique() {
-        dbg!(vec![1.0, 1.01, 1.32, 1.1, 1.2, 1.21, 1.4].unique(0.1));
+    fn with_extrapolation(self, extrapolation: Extrapolation) -> Self {
+        ProfilePorePressure::with_extrapolation(self, extrapolation)
     }
 
-    #[test]
-    fn hm() {
-        dbg!(1.2 - 1.1);
+    fn with_interpolation(self, interpolation: Interpolation) -> Self {
+        ProfilePorePressure::with_interpolation(self, interpolation)
     }
 }
+
+impl_profile_ops!(ProfilePorePressure);
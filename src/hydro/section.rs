@@ -0,0 +1,446 @@
+use crate::hydro::seepage::layer_permeabilities;
+use crate::hydro::{ProfilePorePressure, SeepageError, UNIT_WEIGHT_WATER};
+use crate::profile::{Datum, Extrapolation, Point};
+use crate::soil::profile::SoilProfile;
+
+/// Edge of a cross-section
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Fixed hydraulic head along part of an edge of a cross-section, from `from` to `to` as
+/// horizontal position along the top and bottom edges and as depth along the left and right
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedHead {
+    pub edge: Edge,
+    pub from: f64,
+    pub to: f64,
+    /// Elevation of the water level relative to the datum of the soil profile
+    pub head: f64,
+}
+
+impl FixedHead {
+    #[must_use]
+    pub fn new(edge: Edge, from: f64, to: f64, head: f64) -> Self {
+        Self {
+            edge,
+            from,
+            to,
+            head,
+        }
+    }
+
+    fn covers(&self, edge: Edge, position: f64) -> bool {
+        self.edge == edge
+            && position >= self.from.min(self.to)
+            && position <= self.from.max(self.to)
+    }
+}
+
+/// Impermeable vertical wall, such as a sheet pile wall, at horizontal position `x` from depth
+/// `top` to `bottom`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wall {
+    pub x: f64,
+    pub top: f64,
+    pub bottom: f64,
+}
+
+impl Wall {
+    #[must_use]
+    pub fn new(x: f64, top: f64, bottom: f64) -> Self {
+        Self { x, top, bottom }
+    }
+}
+
+/// Steady-state groundwater flow in a vertical cross-section through the soil profile, from
+/// the ground surface down to bedrock and from zero to `width` horizontally. Layers are
+/// horizontal with the permeability of their soil models, and edges without a fixed head are
+/// impermeable. Solved with finite volumes on a regular grid of cells, using the exact series
+/// and parallel permeability of the layers within and between cells
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionSeepage {
+    pub width: f64,
+    pub cell_size: f64,
+    pub fixed_heads: Vec<FixedHead>,
+    pub walls: Vec<Wall>,
+    pub unit_weight_water: f64,
+    pub max_iterations: usize,
+}
+
+impl SectionSeepage {
+    #[must_use]
+    pub fn new(width: f64) -> Self {
+        Self {
+            width,
+            cell_size: 0.5,
+            fixed_heads: Vec::new(),
+            walls: Vec::new(),
+            unit_weight_water: UNIT_WEIGHT_WATER,
+            max_iterations: 100_000,
+        }
+    }
+    #[must_use]
+    pub fn with_cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+    /// Add a fixed head boundary, taking precedence over those already added where they
+    /// overlap
+    #[must_use]
+    pub fn with_fixed_head(mut self, fixed_head: FixedHead) -> Self {
+        self.fixed_heads.push(fixed_head);
+        self
+    }
+    #[must_use]
+    pub fn with_wall(mut self, wall: Wall) -> Self {
+        self.walls.push(wall);
+        self
+    }
+    #[must_use]
+    pub fn with_unit_weight_water(mut self, unit_weight_water: f64) -> Self {
+        self.unit_weight_water = unit_weight_water;
+        self
+    }
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    fn fixed_head(&self, edge: Edge, position: f64) -> Option<f64> {
+        self.fixed_heads
+            .iter()
+            .rev()
+            .find(|fixed_head| fixed_head.covers(edge, position))
+            .map(|fixed_head| fixed_head.head)
+    }
+
+    /// Head in every cell of the cross-section
+    /// # Errors
+    /// Returns a `SeepageError` if a layer lacks a valid permeability, the section has no
+    /// size, no fixed head boundary covers a cell or the solver does not converge
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss,
+        clippy::too_many_lines
+    )]
+    pub fn solve(&self, soil_profile: &SoilProfile) -> Result<SeepageField, SeepageError> {
+        if !(self.width > 0.0 && self.cell_size > 0.0) {
+            return Err(SeepageError::InvalidSection {
+                width: self.width,
+                cell_size: self.cell_size,
+            });
+        }
+        let bedrock = soil_profile.depth_to_bedrock();
+        let layers = layer_permeabilities(soil_profile, 0.0, bedrock)?;
+
+        // Flow along and across the layers between two depths
+        let transmissivity = |z0: f64, z1: f64| {
+            layers.iter().fold(0.0, |acc, &(top, thickness, k)| {
+                acc + k * ((top + thickness).min(z1) - top.max(z0)).max(0.0)
+            })
+        };
+        let resistance = |z0: f64, z1: f64| {
+            layers.iter().fold(0.0, |acc, &(top, thickness, k)| {
+                acc + ((top + thickness).min(z1) - top.max(z0)).max(0.0) / k
+            })
+        };
+
+        let nx = (self.width / self.cell_size).ceil().max(1.0) as usize;
+        let nz = (bedrock / self.cell_size).ceil().max(1.0) as usize;
+        let dx = self.width / nx as f64;
+        let dz = bedrock / nz as f64;
+        let xs = (0..nx).map(|i| (i as f64 + 0.5) * dx).collect::<Vec<_>>();
+        let depths = (0..nz).map(|j| (j as f64 + 0.5) * dz).collect::<Vec<_>>();
+        let cell = |i: usize, j: usize| j * nx + i;
+
+        // Conductance to the cell to the right and below
+        let mut east = vec![0.0; nx * nz];
+        let mut south = vec![0.0; nx * nz];
+        for j in 0..nz {
+            let (z0, z1) = (j as f64 * dz, (j + 1) as f64 * dz);
+            let row = transmissivity(z0, z1);
+            for i in 0..nx - 1 {
+                let face = i + 1;
+                let blocked = self
+                    .walls
+                    .iter()
+                    .filter(|wall| (wall.x / dx).round() as usize == face)
+                    .fold(0.0, |acc, wall| {
+                        acc + transmissivity(wall.top.max(z0), wall.bottom.min(z1))
+                    });
+                east[cell(i, j)] = (row - blocked).max(0.0) / dx;
+            }
+            if j + 1 < nz {
+                let conductance = dx / resistance(depths[j], depths[j + 1]);
+                for i in 0..nx {
+                    south[cell(i, j)] = conductance;
+                }
+            }
+        }
+
+        // Fixed head boundaries as conductance to the head on the edge
+        let mut boundary = vec![0.0; nx * nz];
+        let mut rhs = vec![0.0; nx * nz];
+        let mut add_boundary = |c: usize, conductance: f64, head: f64| {
+            boundary[c] += conductance;
+            rhs[c] += conductance * head;
+        };
+        let top_heads = xs
+            .iter()
+            .map(|&x| self.fixed_head(Edge::Top, x))
+            .collect::<Vec<_>>();
+        let top_resistance = resistance(0.0, depths[0]);
+        let bottom_resistance = resistance(depths[nz - 1], bedrock);
+        for (i, &x) in xs.iter().enumerate() {
+            if let Some(head) = top_heads[i] {
+                add_boundary(cell(i, 0), dx / top_resistance, head);
+            }
+            if let Some(head) = self.fixed_head(Edge::Bottom, x) {
+                add_boundary(cell(i, nz - 1), dx / bottom_resistance, head);
+            }
+        }
+        for (j, &z) in depths.iter().enumerate() {
+            let conductance = transmissivity(j as f64 * dz, (j + 1) as f64 * dz) / (dx / 2.0);
+            if let Some(head) = self.fixed_head(Edge::Left, z) {
+                add_boundary(cell(0, j), conductance, head);
+            }
+            if let Some(head) = self.fixed_head(Edge::Right, z) {
+                add_boundary(cell(nx - 1, j), conductance, head);
+            }
+        }
+        let total_conductance = boundary.iter().sum::<f64>();
+        if total_conductance <= 0.0 {
+            return Err(SeepageError::NoFixedHead);
+        }
+        // Start from the mean of the fixed heads
+        let start = rhs.iter().sum::<f64>() / total_conductance;
+
+        let mut diagonal = boundary;
+        for j in 0..nz {
+            for i in 0..nx {
+                let c = cell(i, j);
+                if i + 1 < nx {
+                    diagonal[c] += east[c];
+                    diagonal[c + 1] += east[c];
+                }
+                if j + 1 < nz {
+                    diagonal[c] += south[c];
+                    diagonal[c + nx] += south[c];
+                }
+            }
+        }
+        let multiply = |x: &[f64], y: &mut [f64]| {
+            for c in 0..nx * nz {
+                y[c] = diagonal[c] * x[c];
+            }
+            for j in 0..nz {
+                for i in 0..nx {
+                    let c = cell(i, j);
+                    if i + 1 < nx {
+                        y[c] -= east[c] * x[c + 1];
+                        y[c + 1] -= east[c] * x[c];
+                    }
+                    if j + 1 < nz {
+                        y[c] -= south[c] * x[c + nx];
+                        y[c + nx] -= south[c] * x[c];
+                    }
+                }
+            }
+        };
+
+        let heads = conjugate_gradient(&multiply, &diagonal, &rhs, start, self.max_iterations)?;
+
+        // Upward gradient in the top layer from the flow out through the ground surface
+        let surface_permeability = layers[0].2;
+        let exit_gradients = top_heads
+            .iter()
+            .enumerate()
+            .filter_map(|(i, head)| {
+                head.map(|head| {
+                    let flux = (heads[cell(i, 0)] - head) / top_resistance;
+                    Point::new(xs[i], flux / surface_permeability)
+                })
+            })
+            .collect();
+
+        Ok(SeepageField {
+            xs,
+            depths,
+            heads,
+            exit_gradients,
+            datum: soil_profile.datum(),
+            unit_weight_water: self.unit_weight_water,
+        })
+    }
+}
+
+/// Jacobi preconditioned conjugate gradient for a symmetric positive definite system
+fn conjugate_gradient(
+    multiply: &impl Fn(&[f64], &mut [f64]),
+    diagonal: &[f64],
+    rhs: &[f64],
+    start: f64,
+    max_iterations: usize,
+) -> Result<Vec<f64>, SeepageError> {
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+    let n = rhs.len();
+    let tolerance = 1e-12 * dot(rhs, rhs).sqrt();
+
+    let mut x = vec![start; n];
+    let mut product = vec![0.0; n];
+    multiply(&x, &mut product);
+    let mut residual = rhs
+        .iter()
+        .zip(&product)
+        .map(|(b, ax)| b - ax)
+        .collect::<Vec<_>>();
+    let mut z = residual
+        .iter()
+        .zip(diagonal)
+        .map(|(r, d)| r / d)
+        .collect::<Vec<_>>();
+    let mut direction = z.clone();
+    let mut rz = dot(&residual, &z);
+
+    for _ in 0..max_iterations {
+        if dot(&residual, &residual).sqrt() <= tolerance {
+            return Ok(x);
+        }
+        multiply(&direction, &mut product);
+        let alpha = rz / dot(&direction, &product);
+        for k in 0..n {
+            x[k] += alpha * direction[k];
+            residual[k] -= alpha * product[k];
+            z[k] = residual[k] / diagonal[k];
+        }
+        let rz_next = dot(&residual, &z);
+        let beta = rz_next / rz;
+        rz = rz_next;
+        for k in 0..n {
+            direction[k] = z[k] + beta * direction[k];
+        }
+    }
+    if dot(&residual, &residual).sqrt() <= tolerance {
+        Ok(x)
+    } else {
+        Err(SeepageError::NotConverged {
+            iterations: max_iterations,
+        })
+    }
+}
+
+/// Heads from [`SectionSeepage::solve`] in the centre of every cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeepageField {
+    xs: Vec<f64>,
+    depths: Vec<f64>,
+    /// Row by row from the top
+    heads: Vec<f64>,
+    exit_gradients: Vec<Point>,
+    datum: Datum,
+    unit_weight_water: f64,
+}
+
+/// Indices of the cell centres on each side of `value` and the fraction of the way between them
+fn bracket(centres: &[f64], value: f64) -> (usize, usize, f64) {
+    let last = centres.len() - 1;
+    if value <= centres[0] {
+        return (0, 0, 0.0);
+    }
+    if value >= centres[last] {
+        return (last, last, 0.0);
+    }
+    let above = centres.partition_point(|&c| c <= value);
+    let below = above - 1;
+    (
+        below,
+        above,
+        (value - centres[below]) / (centres[above] - centres[below]),
+    )
+}
+
+impl SeepageField {
+    /// Horizontal position of the cell centres
+    #[must_use]
+    pub fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+    /// Depth of the cell centres
+    #[must_use]
+    pub fn depths(&self) -> &[f64] {
+        &self.depths
+    }
+    /// Head in every cell, one row for each depth
+    #[must_use]
+    pub fn heads(&self) -> Vec<Vec<f64>> {
+        self.heads
+            .chunks(self.xs.len())
+            .map(<[f64]>::to_vec)
+            .collect()
+    }
+    /// Pore pressure in every cell, one row for each depth
+    #[must_use]
+    pub fn pore_pressures(&self) -> Vec<Vec<f64>> {
+        self.heads()
+            .iter()
+            .zip(&self.depths)
+            .map(|(row, &depth)| {
+                row.iter()
+                    .map(|&head| self.head_to_pore_pressure(head, depth))
+                    .collect()
+            })
+            .collect()
+    }
+    /// Head at a point, bilinear between the cell centres and constant beyond the outer ones
+    #[must_use]
+    pub fn head(&self, x: f64, depth: f64) -> f64 {
+        let nx = self.xs.len();
+        let (i0, i1, fx) = bracket(&self.xs, x);
+        let (j0, j1, fz) = bracket(&self.depths, depth);
+        let at = |i: usize, j: usize| self.heads[j * nx + i];
+        let upper = at(i0, j0) + fx * (at(i1, j0) - at(i0, j0));
+        let lower = at(i0, j1) + fx * (at(i1, j1) - at(i0, j1));
+        upper + fz * (lower - upper)
+    }
+    fn head_to_pore_pressure(&self, head: f64, depth: f64) -> f64 {
+        self.unit_weight_water * (head - self.datum.elevation(depth))
+    }
+    /// Pore pressure at a point, negative where the head is below the elevation
+    #[must_use]
+    pub fn pore_pressure(&self, x: f64, depth: f64) -> f64 {
+        self.head_to_pore_pressure(self.head(x, depth), depth)
+    }
+    /// Pore pressure along the vertical line at `x`, with a point at each cell centre and
+    /// linear extrapolation to the ground surface and bedrock
+    #[must_use]
+    pub fn pore_pressure_profile(&self, x: f64) -> ProfilePorePressure {
+        ProfilePorePressure::new(
+            self.depths
+                .iter()
+                .map(|&depth| Point::new(depth, self.pore_pressure(x, depth)))
+                .collect(),
+        )
+        .with_extrapolation(Extrapolation::Linear)
+    }
+    /// Upward hydraulic gradient at the ground surface against horizontal position, for every
+    /// cell with a fixed head on the top edge. Negative where water flows into the ground
+    #[must_use]
+    pub fn exit_gradients(&self) -> &[Point] {
+        &self.exit_gradients
+    }
+    /// Largest upward hydraulic gradient at the ground surface
+    #[must_use]
+    pub fn max_exit_gradient(&self) -> Option<f64> {
+        self.exit_gradients
+            .iter()
+            .map(|p| p.y)
+            .max_by(f64::total_cmp)
+    }
+}
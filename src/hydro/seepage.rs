@@ -0,0 +1,205 @@
+use std::fmt;
+
+use crate::hydro::{ProfilePorePressure, UNIT_WEIGHT_WATER};
+use crate::profile::Point;
+use crate::soil::profile::SoilProfile;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum SeepageError {
+    /// The soil layer at this index has no permeability
+    MissingPermeability { layer: usize },
+    /// The soil layer at this index has a permeability that is zero, negative or not finite
+    InvalidPermeability { layer: usize, permeability: f64 },
+    /// The top boundary is not above the bottom boundary, or they lie outside the soil profile
+    InvalidBoundaries { top: f64, bottom: f64 },
+    /// The width or cell size of a cross-section is not positive
+    InvalidSection { width: f64, cell_size: f64 },
+    /// No boundary of the cross-section has a fixed head
+    NoFixedHead,
+    /// The iterative solver did not reach the tolerance within this many iterations
+    NotConverged { iterations: usize },
+}
+
+impl fmt::Display for SeepageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPermeability { layer } => {
+                write!(f, "soil layer {layer} has no permeability")
+            }
+            Self::InvalidPermeability {
+                layer,
+                permeability,
+            } => write!(
+                f,
+                "soil layer {layer} has invalid permeability {permeability}"
+            ),
+            Self::InvalidBoundaries { top, bottom } => {
+                write!(f, "invalid seepage boundaries at depths {top} and {bottom}")
+            }
+            Self::InvalidSection { width, cell_size } => write!(
+                f,
+                "invalid cross-section of width {width} with cell size {cell_size}"
+            ),
+            Self::NoFixedHead => write!(f, "no boundary has a fixed head"),
+            Self::NotConverged { iterations } => {
+                write!(
+                    f,
+                    "seepage solver did not converge in {iterations} iterations"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeepageError {}
+
+/// Steady-state vertical seepage between two boundaries with fixed hydraulic head, such as a
+/// top aquifer and an underlying aquifer on bedrock. Heads are elevations of the water level
+/// relative to the datum of the soil profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SteadySeepage {
+    pub top_head: f64,
+    pub bottom_head: f64,
+    /// Depth of the top boundary, the ground surface if not given
+    pub top_depth: Option<f64>,
+    /// Depth of the bottom boundary, the bedrock if not given
+    pub bottom_depth: Option<f64>,
+    pub unit_weight_water: f64,
+}
+
+impl SteadySeepage {
+    #[must_use]
+    pub fn new(top_head: f64, bottom_head: f64) -> Self {
+        Self {
+            top_head,
+            bottom_head,
+            top_depth: None,
+            bottom_depth: None,
+            unit_weight_water: UNIT_WEIGHT_WATER,
+        }
+    }
+    #[must_use]
+    pub fn with_boundaries(mut self, top_depth: f64, bottom_depth: f64) -> Self {
+        self.top_depth = Some(top_depth);
+        self.bottom_depth = Some(bottom_depth);
+        self
+    }
+    #[must_use]
+    pub fn with_unit_weight_water(mut self, unit_weight_water: f64) -> Self {
+        self.unit_weight_water = unit_weight_water;
+        self
+    }
+
+    fn boundaries(&self, soil_profile: &SoilProfile) -> (f64, f64) {
+        (
+            self.top_depth.unwrap_or(0.0),
+            self.bottom_depth
+                .unwrap_or_else(|| soil_profile.depth_to_bedrock()),
+        )
+    }
+
+    /// Downward specific discharge through the layers in m/s, negative for upward flow
+    /// # Errors
+    /// Returns a `SeepageError` if a layer between the boundaries lacks a valid permeability or
+    /// the boundaries are invalid
+    pub fn flux(&self, soil_profile: &SoilProfile) -> Result<f64, SeepageError> {
+        let (top, bottom) = self.boundaries(soil_profile);
+        let resistance = hydraulic_resistance(soil_profile, top, bottom)?;
+
+        Ok((self.top_head - self.bottom_head) / resistance)
+    }
+
+    /// Pore pressure profile for steady-state flow. Between the boundaries the head varies
+    /// linearly within each layer with equal flux through every layer, outside them the pore
+    /// pressure is hydrostatic about the boundary head and not below zero. With the top head
+    /// below the top boundary, the pore pressure goes linearly from zero at the ground surface
+    /// to the suction at the top boundary
+    /// # Errors
+    /// See [`SteadySeepage::flux`]
+    pub fn solve(&self, soil_profile: &SoilProfile) -> Result<ProfilePorePressure, SeepageError> {
+        let (top, bottom) = self.boundaries(soil_profile);
+        let layers = layer_permeabilities(soil_profile, top, bottom)?;
+        let flux = self.flux(soil_profile)?;
+        let datum = soil_profile.datum();
+        let gamma_w = self.unit_weight_water;
+        let u = |depth: f64, head: f64| gamma_w * (head - datum.elevation(depth));
+
+        let mut points = Vec::with_capacity(layers.len() + 4);
+
+        // Hydrostatic above the top boundary, dry above the water table
+        if top > 0.0 {
+            let water_table = datum.depth(self.top_head).clamp(0.0, top);
+            points.push(Point::new(0.0, u(0.0, self.top_head).max(0.0)));
+            if water_table > 0.0 && water_table < top {
+                points.push(Point::new(water_table, 0.0));
+            }
+        }
+
+        let mut head = self.top_head;
+        points.push(Point::new(top, u(top, head)));
+        for (z0, thickness, permeability) in layers {
+            head -= flux * thickness / permeability;
+            points.push(Point::new(z0 + thickness, u(z0 + thickness, head)));
+        }
+
+        let bedrock = soil_profile.depth_to_bedrock();
+        if bedrock > bottom {
+            points.push(Point::new(bedrock, u(bedrock, self.bottom_head)));
+        }
+
+        Ok(ProfilePorePressure::new(points))
+    }
+}
+
+/// Depth, thickness and permeability of every layer part between `top` and `bottom`
+pub(crate) fn layer_permeabilities(
+    soil_profile: &SoilProfile,
+    top: f64,
+    bottom: f64,
+) -> Result<Vec<(f64, f64, f64)>, SeepageError> {
+    if top < 0.0 || top >= bottom || bottom > soil_profile.depth_to_bedrock() {
+        return Err(SeepageError::InvalidBoundaries { top, bottom });
+    }
+
+    let layers = soil_profile
+        .layer_bounds()
+        .iter()
+        .enumerate()
+        .filter(|(_, &(z0, z1, _))| z1 > top && z0 < bottom)
+        .map(|(layer, &(z0, z1, soil_layer))| {
+            let permeability = soil_layer
+                .permeability()
+                .ok_or(SeepageError::MissingPermeability { layer })?;
+            if !(permeability.is_finite() && permeability > 0.0) {
+                return Err(SeepageError::InvalidPermeability {
+                    layer,
+                    permeability,
+                });
+            }
+            Ok((z0.max(top), z1.min(bottom) - z0.max(top), permeability))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if layers.is_empty() {
+        return Err(SeepageError::InvalidBoundaries { top, bottom });
+    }
+    Ok(layers)
+}
+
+/// Resistance to vertical flow between `top` and `bottom` in seconds, the sum of thickness over
+/// permeability for the layers in between
+/// # Errors
+/// Returns a `SeepageError` if a layer between the depths lacks a valid permeability, or the
+/// depths are not in order within the soil profile
+pub fn hydraulic_resistance(
+    soil_profile: &SoilProfile,
+    top: f64,
+    bottom: f64,
+) -> Result<f64, SeepageError> {
+    Ok(layer_permeabilities(soil_profile, top, bottom)?
+        .iter()
+        .fold(0.0, |acc, (_, thickness, permeability)| {
+            acc + thickness / permeability
+        }))
+}
@@ -1,9 +1,17 @@
+use std::f64::consts::PI;
+
 use approx::assert_relative_eq;
 use rstest::rstest;
 
-use crate::profile::{Point, Profile};
+use crate::profile::{Datum, DepthProfile, Extrapolation, Point, Profile};
+use crate::soil::{layer::SoilLayer, model::Clay, profile::SoilProfile};
 
-use super::ProfilePorePressure;
+use super::{
+    Aquifer, Boundary, Capillary, Consolidation, ConsolidationError, Drawdown, DrawdownShape, Edge,
+    FilterLevel, FixedHead, HeadProfile, Piezometer, PiezometerError, PiezometerSet,
+    ProfilePorePressure, RadialFlow, SectionSeepage, SeepageError, SteadySeepage, Timestamp,
+    Uplift, UpliftError, Wall, Well, WellField,
+};
 
 #[test]
 fn default() {
@@ -80,3 +88,913 @@ fn subtraction_dbg() {
     let rhs = ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 100.0)]);
     dbg!(lhs - rhs);
 }
+
+#[rstest]
+#[case(2.0, 0.0, 0.0)]
+#[case(2.0, 2.0, 0.0)]
+#[case(2.0, 7.0, 50.0)]
+#[case(2.0, 30.0, 280.0)]
+#[case(-1.0, 0.0, 10.0)]
+#[case(-1.0, 10.0, 110.0)]
+#[case(15.0, 12.0, 0.0)]
+#[case(15.0, 20.0, 50.0)]
+#[case(10.0, 20.0, 100.0)]
+fn hydrostatic(#[case] water_table: f64, #[case] depth: f64, #[case] expected: f64) {
+    let profile = ProfilePorePressure::hydrostatic(water_table, 10.0, 10.0);
+    assert_relative_eq!(profile.eval(depth), expected);
+}
+
+#[rstest]
+#[case(1.0, 0.0)]
+#[case(3.0, 20.0)]
+#[case(4.5, 10.0)]
+#[case(6.0, 0.0)]
+#[case(8.0, 0.0)]
+#[case(12.0, 40.0)]
+fn perched(#[case] depth: f64, #[case] expected: f64) {
+    let profile = ProfilePorePressure::perched(1.0, (3.0, 6.0), 8.0, 20.0, 10.0);
+    assert_relative_eq!(profile.eval(depth), expected);
+}
+
+#[rstest]
+#[case(0.0, 0.0)]
+#[case(1.0, 5.0)]
+#[case(4.0, 30.0)]
+#[case(5.0, 45.0)]
+#[case(10.0 - 1e-9, 100.0)]
+#[case(10.0, 120.0)]
+#[case(15.0, 170.0)]
+fn artesian_aquifers(#[case] depth: f64, #[case] expected: f64) {
+    let profile = ProfilePorePressure::aquifers(
+        &[
+            Aquifer::new(2.0, 4.0, 1.0),
+            Aquifer::new(10.0, 20.0, -2.0),
+            Aquifer::new(6.0, 10.0, 0.0),
+        ],
+        10.0,
+    );
+    assert_relative_eq!(profile.eval(depth), expected, epsilon = 1e-6);
+}
+
+fn hydrostatic_to_20m() -> ProfilePorePressure {
+    ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(20.0, 200.0)])
+}
+
+#[rstest]
+#[case(Drawdown::new(-50.0).with_shape(DrawdownShape::Uniform), 2.0, 0.0)]
+#[case(Drawdown::new(-50.0).with_shape(DrawdownShape::Uniform), 10.0, 50.0)]
+#[case(Drawdown::new(-50.0).with_shape(DrawdownShape::Uniform).with_clamp_at_zero(false), 2.0, -30.0)]
+#[case(Drawdown::new(-50.0).with_influence_height(5.0), 15.0, 150.0)]
+#[case(Drawdown::new(-50.0).with_influence_height(5.0), 17.5, 150.0)]
+#[case(Drawdown::new(-50.0).with_influence_height(5.0), 20.0, 150.0)]
+#[case(Drawdown::new(-50.0).with_bottom(10.0), 10.0, 50.0)]
+#[case(Drawdown::new(-50.0).with_bottom(10.0), 20.0, 150.0)]
+#[case(Drawdown::new(-50.0).with_shape(DrawdownShape::Exponential).with_samples(201), 20.0, 150.0)]
+#[case(Drawdown::new(-50.0).with_shape(DrawdownShape::Exponential).with_samples(201), 10.0, 100.0 - 50.0 * (-1.0_f64).exp())]
+#[case(Drawdown::new(-30.0).with_shape(DrawdownShape::TopAquifer { aquifer_bottom: 5.0 }), 4.0, 10.0)]
+#[case(Drawdown::new(-30.0).with_shape(DrawdownShape::TopAquifer { aquifer_bottom: 5.0 }), 10.0, 85.0)]
+#[case(Drawdown::new(-30.0).with_shape(DrawdownShape::TopAquifer { aquifer_bottom: 5.0 }), 16.0, 160.0)]
+#[case(Drawdown::new(-30.0).with_shape(DrawdownShape::Custom(DepthProfile::new(vec![Point::new(5.0, 0.0), Point::new(15.0, 1.0)]))), 10.0, 85.0)]
+fn apply_drawdown(#[case] drawdown: Drawdown, #[case] eval_depth: f64, #[case] expected: f64) {
+    let profile = hydrostatic_to_20m().apply_drawdown(&drawdown);
+    assert_relative_eq!(profile.eval(eval_depth), expected, epsilon = 1e-3);
+}
+
+#[test]
+fn drawdown_keeps_profile_below_bottom() {
+    let profile = ProfilePorePressure::hydrostatic(0.0, 30.0, 10.0);
+    let full = profile.apply_drawdown(&Drawdown::new(-50.0));
+    let limited = profile.apply_drawdown(&Drawdown::new(-50.0).with_bottom(20.0));
+    assert_relative_eq!(limited.eval(20.0), 150.0);
+    assert_relative_eq!(limited.eval(25.0), 200.0);
+    assert_relative_eq!(limited.eval(30.0), 250.0);
+    assert_relative_eq!(full.eval(30.0), 250.0);
+
+    let top_aquifer = Drawdown::new(-30.0).with_shape(DrawdownShape::TopAquifer {
+        aquifer_bottom: 5.0,
+    });
+    let with_bottom = profile.apply_drawdown(&top_aquifer.clone().with_bottom(20.0));
+    let without_bottom = profile.apply_drawdown(&top_aquifer);
+    for depth in [4.0, 10.0, 20.0, 30.0] {
+        assert_relative_eq!(with_bottom.eval(depth), without_bottom.eval(depth));
+    }
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+fn drawdown_samples_at_least_two(#[case] samples: usize) {
+    let drawdown = Drawdown::new(-50.0)
+        .with_shape(DrawdownShape::Exponential)
+        .with_samples(samples);
+    assert_eq!(drawdown.samples, 2);
+    let profile = hydrostatic_to_20m().apply_drawdown(&drawdown);
+    assert_relative_eq!(profile.eval(20.0), 150.0);
+}
+
+#[test]
+fn drawdown_clamp_inserts_phreatic_surface() {
+    let profile = hydrostatic_to_20m()
+        .apply_drawdown(&Drawdown::new(-50.0).with_shape(DrawdownShape::Uniform));
+    assert_eq!(profile.xs(), vec![0.0, 5.0, 20.0]);
+    assert_eq!(profile.ys(), vec![0.0, 0.0, 150.0]);
+}
+
+fn underdrained_clay() -> SoilProfile {
+    SoilProfile::default().with_soil_layers(vec![
+        SoilLayer::new(
+            10.0,
+            Box::new(Clay {
+                permeability: Some(1e-9),
+                ..Default::default()
+            }),
+        ),
+        SoilLayer::new(
+            10.0,
+            Box::new(Clay {
+                permeability: Some(1e-8),
+                ..Default::default()
+            }),
+        ),
+    ])
+}
+
+#[rstest]
+#[case(0.0, 0.0)]
+#[case(5.0, 50.0 - 10.0 * 10.0 / 1.1 / 2.0)]
+#[case(10.0, 100.0 - 10.0 * 10.0 / 1.1)]
+#[case(20.0, 100.0)]
+fn steady_seepage(#[case] depth: f64, #[case] expected: f64) {
+    let seepage = SteadySeepage::new(0.0, -10.0).with_unit_weight_water(10.0);
+    let profile = seepage.solve(&underdrained_clay()).unwrap();
+
+    assert_relative_eq!(profile.eval(depth), expected, epsilon = 1e-9);
+    assert_relative_eq!(seepage.flux(&underdrained_clay()).unwrap(), 10.0 / 1.1e10);
+}
+
+#[test]
+fn steady_seepage_flux_continuity() {
+    let seepage = SteadySeepage::new(0.0, -10.0).with_unit_weight_water(10.0);
+    let profile = seepage.solve(&underdrained_clay()).unwrap();
+    let flux = |depth: f64, k: f64| -k * (1.0 - profile.gradient(depth) / 10.0);
+
+    assert_relative_eq!(flux(5.0, 1e-9), flux(15.0, 1e-8));
+}
+
+#[test]
+fn steady_seepage_inner_boundaries() {
+    let soil_profile = underdrained_clay().with_datum(Datum::new(100.0));
+    let profile = SteadySeepage::new(99.0, 95.0)
+        .with_boundaries(3.0, 10.0)
+        .with_unit_weight_water(10.0)
+        .solve(&soil_profile)
+        .unwrap();
+
+    assert_eq!(profile.xs(), vec![0.0, 1.0, 3.0, 10.0, 20.0]);
+    assert_eq!(profile.ys(), vec![0.0, 0.0, 20.0, 50.0, 150.0]);
+}
+
+#[test]
+fn steady_seepage_head_below_top_boundary() {
+    let soil_profile = underdrained_clay().with_datum(Datum::new(100.0));
+    let profile = SteadySeepage::new(92.0, 95.0)
+        .with_boundaries(5.0, 10.0)
+        .with_unit_weight_water(10.0)
+        .solve(&soil_profile)
+        .unwrap();
+
+    assert_eq!(profile.xs(), vec![0.0, 5.0, 10.0, 20.0]);
+    assert_relative_eq!(profile.eval(5.0), -30.0);
+    assert_relative_eq!(profile.eval_left(5.0), profile.eval(5.0));
+}
+
+#[rstest]
+#[case(SteadySeepage::new(0.0, -10.0).with_boundaries(5.0, 2.0), SeepageError::InvalidBoundaries { top: 5.0, bottom: 2.0 })]
+#[case(SteadySeepage::new(0.0, -10.0).with_boundaries(0.0, 25.0), SeepageError::InvalidBoundaries { top: 0.0, bottom: 25.0 })]
+fn steady_seepage_invalid(#[case] seepage: SteadySeepage, #[case] expected: SeepageError) {
+    assert_eq!(seepage.solve(&underdrained_clay()).unwrap_err(), expected);
+}
+
+#[test]
+fn steady_seepage_missing_permeability() {
+    let soil_profile = SoilProfile::default()
+        .with_soil_layers(vec![SoilLayer::new(10.0, Box::new(Clay::default()))]);
+    assert_eq!(
+        SteadySeepage::new(0.0, -5.0)
+            .solve(&soil_profile)
+            .unwrap_err(),
+        SeepageError::MissingPermeability { layer: 0 }
+    );
+}
+
+fn clay_layer(thickness: f64) -> SoilProfile {
+    SoilProfile::default()
+        .with_soil_layers(vec![SoilLayer::new(
+            thickness,
+            Box::new(Clay {
+                permeability: Some(1e-9),
+                ..Default::default()
+            }),
+        )])
+        .with_pore_pressure_profile(ProfilePorePressure::hydrostatic(0.0, thickness, 10.0))
+}
+
+#[rstest]
+#[case(0.05, 0.252)]
+#[case(0.2, 0.504)]
+#[case(0.5, 0.764)]
+fn consolidation_single_drainage(#[case] time_factor: f64, #[case] degree: f64) {
+    let soil_profile = clay_layer(10.0);
+    let cv = 1e-6;
+    let time = time_factor * 100.0 / cv;
+    let profiles = Consolidation::new(Boundary::drained(-100.0), Boundary::Undrained)
+        .with_consolidation_coefficients(vec![cv])
+        .solve(&soil_profile, &[time])
+        .unwrap();
+
+    let change = &profiles[0] - soil_profile.pore_pressure_profile();
+    assert_relative_eq!(
+        change.integrate(0.0, 10.0) / -1000.0,
+        degree,
+        epsilon = 0.01
+    );
+}
+
+#[test]
+fn consolidation_reaches_steady_seepage() {
+    let soil_profile = clay_layer(10.0);
+    let profiles = Consolidation::new(Boundary::drained(0.0), Boundary::drained(-50.0))
+        .with_unit_weight_water(10.0)
+        .solve(&soil_profile, &[0.0, 1e12])
+        .unwrap();
+    let steady = SteadySeepage::new(0.0, -5.0)
+        .with_unit_weight_water(10.0)
+        .solve(&soil_profile)
+        .unwrap();
+
+    assert_relative_eq!(profiles[0].eval(5.0), 50.0);
+    assert_relative_eq!(profiles[0].eval(10.0), 50.0);
+    for depth in [2.5, 5.0, 7.5] {
+        assert_relative_eq!(profiles[1].eval(depth), steady.eval(depth), epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn consolidation_time_varying_boundary() {
+    let soil_profile = clay_layer(10.0);
+    let bottom = Boundary::Drained(vec![Point::new(0.0, 0.0), Point::new(1e6, -50.0)]);
+    let profiles = Consolidation::new(Boundary::drained(0.0), bottom)
+        .solve(&soil_profile, &[5e5, 1e6])
+        .unwrap();
+
+    assert_relative_eq!(profiles[0].eval(10.0), 75.0);
+    assert_relative_eq!(profiles[1].eval(10.0), 50.0);
+}
+
+#[test]
+fn consolidation_unsorted_times() {
+    let soil_profile = clay_layer(10.0);
+    let consolidation = Consolidation::new(Boundary::drained(0.0), Boundary::drained(-50.0));
+    let sorted = consolidation.solve(&soil_profile, &[1e6, 1e7]).unwrap();
+    let unsorted = consolidation.solve(&soil_profile, &[1e7, 1e6]).unwrap();
+
+    assert_eq!(unsorted[0].points(), sorted[1].points());
+    assert_eq!(unsorted[1].points(), sorted[0].points());
+    assert!(unsorted[1].eval(9.0) > unsorted[0].eval(9.0));
+}
+
+#[test]
+fn consolidation_keeps_extrapolation() {
+    let soil_profile = clay_layer(10.0);
+    let profiles = Consolidation::new(Boundary::drained(0.0), Boundary::Undrained)
+        .solve(&soil_profile, &[1e6])
+        .unwrap();
+
+    assert_eq!(profiles[0].extrapolation(), Extrapolation::Linear);
+    assert_relative_eq!(profiles[0].eval(12.0), 120.0, epsilon = 1e-9);
+}
+
+#[test]
+fn settlement_curve_increases_towards_final_settlement() {
+    let soil_profile = clay_layer(10.0);
+    let consolidation = Consolidation::new(Boundary::drained(0.0), Boundary::drained(-50.0));
+    let curve = consolidation
+        .settlement_curve(&soil_profile, &[0.0, 1e7, 1e8, 1e12])
+        .unwrap();
+    let steady = SteadySeepage::new(0.0, -5.0)
+        .with_unit_weight_water(10.0)
+        .solve(&soil_profile)
+        .unwrap();
+
+    assert!(curve.windows(2).all(|w| w[1].y > w[0].y));
+    assert_relative_eq!(
+        curve[3].y,
+        soil_profile.compute_settlement(&steady),
+        max_relative = 1e-3
+    );
+}
+
+#[rstest]
+#[case(Consolidation::new(Boundary::Undrained, Boundary::Undrained).with_consolidation_coefficients(vec![]), ConsolidationError::CoefficientCount { expected: 1, found: 0 })]
+#[case(Consolidation::new(Boundary::Undrained, Boundary::Undrained).with_consolidation_coefficients(vec![-1.0]), ConsolidationError::InvalidCoefficient { layer: 0, value: -1.0 })]
+fn consolidation_invalid(
+    #[case] consolidation: Consolidation,
+    #[case] expected: ConsolidationError,
+) {
+    assert_eq!(
+        consolidation.solve(&clay_layer(10.0), &[1.0]).unwrap_err(),
+        expected
+    );
+}
+
+#[rstest]
+#[case(0.01, 4.037_929_576_538_114)]
+#[case(0.5, 0.559_773_594_776_160_8)]
+#[case(1.0, 0.219_383_934_395_520_3)]
+#[case(5.0, 0.001_148_295_591_275_325_9)]
+fn well_function(#[case] u: f64, #[case] expected: f64) {
+    assert_relative_eq!(super::well_function(u), expected, max_relative = 1e-12);
+}
+
+#[rstest]
+#[case(10.0, 0.01 / (2.0 * PI * 1e-3) * 10.0_f64.ln())]
+#[case(100.0, 0.0)]
+#[case(200.0, 0.0)]
+fn thiem(#[case] distance: f64, #[case] expected: f64) {
+    let flow = RadialFlow::Thiem {
+        transmissivity: 1e-3,
+        radius_of_influence: 100.0,
+    };
+    assert_relative_eq!(flow.drawdown(0.01, distance, 0.0), expected);
+}
+
+#[test]
+fn theis_approaches_cooper_jacob() {
+    let (transmissivity, storativity) = (1e-3, 1e-4);
+    let flow = RadialFlow::Theis {
+        transmissivity,
+        storativity,
+    };
+    let (r, t) = (10.0, 1e6);
+    let cooper_jacob = 0.01 / (4.0 * PI * transmissivity)
+        * (2.25 * transmissivity * t / (r * r * storativity)).ln();
+
+    assert_relative_eq!(flow.drawdown(0.01, r, t), cooper_jacob, max_relative = 1e-3);
+    assert_relative_eq!(flow.drawdown(0.01, r, 0.0), 0.0);
+}
+
+#[test]
+fn dupuit_approaches_thiem_for_small_drawdown() {
+    let dupuit = RadialFlow::Dupuit {
+        permeability: 1e-4,
+        saturated_thickness: 20.0,
+        radius_of_influence: 200.0,
+    };
+    let thiem = RadialFlow::Thiem {
+        transmissivity: 1e-4 * 20.0,
+        radius_of_influence: 200.0,
+    };
+    assert_relative_eq!(
+        dupuit.drawdown(1e-4, 20.0, 0.0),
+        thiem.drawdown(1e-4, 20.0, 0.0),
+        max_relative = 1e-3
+    );
+}
+
+#[test]
+fn well_field_superposition() {
+    let flow = RadialFlow::Theis {
+        transmissivity: 1e-3,
+        storativity: 1e-4,
+    };
+    let field = WellField::new(
+        vec![
+            Well::new(Point::new(-10.0, 0.0), 0.01),
+            Well::new(Point::new(10.0, 0.0), 0.01),
+        ],
+        flow,
+    );
+    let single = flow.drawdown(0.01, 50.0_f64.hypot(10.0), 1e5);
+    let point = Point::new(0.0, 50.0);
+
+    assert_relative_eq!(field.drawdown(point, 1e5), 2.0 * single);
+    assert_relative_eq!(
+        field.boundary_drawdown(point, 1e5, 10.0).change,
+        -20.0 * single
+    );
+}
+
+#[test]
+fn dupuit_well_field() {
+    let flow = RadialFlow::Dupuit {
+        permeability: 1e-4,
+        saturated_thickness: 20.0,
+        radius_of_influence: 200.0,
+    };
+    let well = Well::excavation(Point::new(0.0, 0.0), 2e-3, 400.0 * PI);
+    let field = WellField::new(vec![well, well], flow);
+    let potential = 2.0 * 2e-3 / (PI * 1e-4) * (200.0_f64 / 50.0).ln();
+
+    assert_relative_eq!(well.radius, 20.0);
+    assert_relative_eq!(
+        field.drawdown(Point::new(30.0, 40.0), 0.0),
+        20.0 - (400.0 - potential).sqrt()
+    );
+    assert_relative_eq!(
+        field.drawdown(Point::new(1.0, 0.0), 0.0),
+        field.drawdown(Point::new(20.0, 0.0), 0.0)
+    );
+}
+
+#[rstest]
+#[case(0.01)]
+#[case(0.1)]
+#[case(1.0)]
+fn leaky_well_function_without_leakage_is_theis(#[case] u: f64) {
+    assert_relative_eq!(
+        super::leaky_well_function(u, 1e-6),
+        super::well_function(u),
+        max_relative = 1e-4
+    );
+    assert_relative_eq!(super::leaky_well_function(u, 0.0), super::well_function(u));
+}
+
+#[rstest]
+#[case(0.1, 2.0 * 2.427_069_024_702_017)]
+#[case(1.0, 2.0 * 0.421_024_438_240_708_3)]
+#[case(2.0, 2.0 * 0.113_893_872_749_533_4)]
+fn leaky_well_function_steady_state(#[case] rho: f64, #[case] expected: f64) {
+    assert_relative_eq!(
+        super::leaky_well_function(0.0, rho),
+        expected,
+        max_relative = 1e-6
+    );
+    assert!(super::leaky_well_function(0.1, rho) < expected);
+}
+
+#[rstest]
+#[case(0.0)]
+#[case(0.5)]
+fn well_functions_vanish_for_infinite_u(#[case] rho: f64) {
+    assert_relative_eq!(super::well_function(f64::INFINITY), 0.0);
+    assert_relative_eq!(super::leaky_well_function(f64::INFINITY, rho), 0.0);
+}
+
+#[rstest]
+#[case(RadialFlow::Thiem { transmissivity: 1e-3, radius_of_influence: 100.0 })]
+#[case(RadialFlow::Dupuit { permeability: 1e-4, saturated_thickness: 20.0, radius_of_influence: 100.0 })]
+fn drawdown_at_well_centre(#[case] flow: RadialFlow) {
+    let at_centre = flow.drawdown(1e-3, 0.0, 0.0);
+    assert!(at_centre.is_finite());
+    assert_relative_eq!(at_centre, flow.drawdown(1e-3, 0.1, 0.0));
+}
+
+#[test]
+fn hantush_jacob_from_aquitard() {
+    let flow = RadialFlow::hantush_jacob(&underdrained_clay(), 10.0, 1e-3, 1e-4).unwrap();
+    let RadialFlow::HantushJacob { leakage_factor, .. } = flow else {
+        panic!("expected a leaky aquifer")
+    };
+    assert_relative_eq!(leakage_factor, (1e-3 * 1e10_f64).sqrt());
+
+    // Leakage limits the drawdown below the Theis solution and makes it reach a steady state
+    let theis = RadialFlow::Theis {
+        transmissivity: 1e-3,
+        storativity: 1e-4,
+    };
+    assert!(flow.drawdown(0.01, 100.0, 1e9) < theis.drawdown(0.01, 100.0, 1e9));
+    assert_relative_eq!(
+        flow.drawdown(0.01, 100.0, 1e9),
+        flow.drawdown(0.01, 100.0, 1e12),
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(flow.drawdown(0.01, 100.0, 0.0), 0.0);
+
+    assert_eq!(
+        RadialFlow::hantush_jacob(
+            &SoilProfile::default()
+                .with_soil_layers(vec![SoilLayer::new(20.0, Box::new(Clay::default()))]),
+            10.0,
+            1e-3,
+            1e-4
+        )
+        .unwrap_err(),
+        SeepageError::MissingPermeability { layer: 0 }
+    );
+}
+
+#[test]
+fn leaky_pore_pressure_profile() {
+    let soil_profile = underdrained_clay()
+        .with_pore_pressure_profile(ProfilePorePressure::hydrostatic(0.0, 20.0, 10.0));
+    let flow = RadialFlow::hantush_jacob(&soil_profile, 10.0, 1e-3, 1e-4).unwrap();
+    let field = WellField::new(vec![Well::new(Point::new(0.0, 0.0), 0.01)], flow);
+    let point = Point::new(100.0, 0.0);
+    let change = field.pore_pressure_change(point, 1e6, 10.0);
+    let profile = field.leaky_pore_pressure_profile(&soil_profile, 10.0, point, 1e6, 10.0);
+
+    assert_relative_eq!(profile.eval(0.0), 0.0);
+    assert_relative_eq!(profile.eval(5.0), 50.0 + change / 2.0, epsilon = 1e-9);
+    assert_relative_eq!(profile.eval(10.0), 100.0 + change, epsilon = 1e-9);
+    assert_relative_eq!(profile.eval(20.0), 200.0 + change, epsilon = 1e-9);
+}
+
+#[rstest]
+#[case("1970-01-01", 0.0)]
+#[case("2000-03-01", 951_868_800.0)]
+#[case("2024-02-29 12:30", 1_709_209_800.0)]
+#[case("2024-02-29T12:30:15", 1_709_209_815.0)]
+#[case("1969-12-31", -86_400.0)]
+fn timestamp(#[case] text: &str, #[case] seconds: f64) {
+    let timestamp = Timestamp::parse(text).unwrap();
+    assert_relative_eq!(timestamp.0, seconds);
+    assert_eq!(
+        Timestamp::from_date(
+            text[0..4].parse().unwrap(),
+            text[5..7].parse().unwrap(),
+            text[8..10].parse().unwrap()
+        )
+        .date(),
+        timestamp.date()
+    );
+}
+
+#[rstest]
+#[case("2023-02-29")]
+#[case("2023-13-01")]
+#[case("2023-01-01 24:00")]
+#[case("01.01.2023")]
+fn invalid_timestamp(#[case] text: &str) {
+    assert_eq!(
+        Timestamp::parse(text).unwrap_err(),
+        PiezometerError::InvalidTimestamp {
+            text: text.to_owned()
+        }
+    );
+}
+
+const PIEZOMETER_CSV: &str = "piezometer,elevation,time,pore_pressure
+P1,95.0,2023-01-01,40.0
+P1,95.0,2023-07-01,30.0
+P1,95.0,2024-01-01,50.0
+P2,85.0,2023-01-01,150.0
+P2,85.0,2023-07-01,120.0
+P2,85.0,2024-01-01,140.0
+";
+
+fn piezometers() -> PiezometerSet {
+    PiezometerSet::from_csv(PIEZOMETER_CSV)
+        .unwrap()
+        .with_datum(Datum::new(100.0))
+}
+
+#[test]
+fn piezometers_from_csv() {
+    let set = piezometers();
+    assert_eq!(set.piezometers.len(), 2);
+    assert_eq!(set.piezometers[1].name, "P2");
+    assert_eq!(set.piezometers[1].level, FilterLevel::Elevation(85.0));
+    assert_relative_eq!(set.piezometers[1].level.depth(set.datum), 15.0);
+    assert_eq!(set.piezometers[0].readings().len(), 3);
+
+    assert_eq!(
+        PiezometerSet::from_csv("piezometer,depth,pore_pressure\n").unwrap_err(),
+        PiezometerError::MissingColumn { column: "time" }
+    );
+    assert_eq!(
+        PiezometerSet::from_csv("piezometer;depth;time;pore_pressure\nP1;5;2023-01-01;x")
+            .unwrap_err(),
+        PiezometerError::InvalidField {
+            line: 2,
+            column: "pore_pressure"
+        }
+    );
+}
+
+#[test]
+fn piezometer_profile_at() {
+    let set = piezometers();
+    let start = Timestamp::from_date(2023, 1, 1);
+    let summer = Timestamp::from_date(2023, 7, 1);
+    let time = Timestamp(f64::midpoint(start.0, summer.0));
+    let profile = set.profile_at(time).unwrap();
+
+    assert_eq!(profile.xs(), vec![5.0, 15.0]);
+    assert_relative_eq!(profile.eval(5.0), 35.0);
+    assert_relative_eq!(profile.eval(10.0), 85.0);
+    assert_eq!(
+        set.profile_at(Timestamp::from_date(2025, 1, 1))
+            .unwrap_err(),
+        PiezometerError::NoReadings
+    );
+}
+
+#[test]
+fn piezometer_seasonal_envelope() {
+    let set = piezometers();
+    let all_year = set
+        .seasonal_envelope(&(1..=12).collect::<Vec<_>>())
+        .unwrap();
+    assert_eq!(all_year.min.ys(), vec![30.0, 120.0]);
+    assert_eq!(all_year.max.ys(), vec![50.0, 150.0]);
+
+    let winter = set.seasonal_envelope(&[12, 1, 2]).unwrap();
+    assert_eq!(winter.min.ys(), vec![40.0, 140.0]);
+    assert_eq!(winter.max.ys(), vec![50.0, 150.0]);
+
+    assert_eq!(
+        set.seasonal_envelope(&[3]).unwrap_err(),
+        PiezometerError::NoReadings
+    );
+}
+
+#[test]
+fn piezometer_readings_are_sorted() {
+    let piezometer = Piezometer::new("P1", FilterLevel::Depth(5.0)).with_readings(vec![
+        (Timestamp::from_date(2023, 2, 1), 20.0),
+        (Timestamp::from_date(2023, 1, 1), 10.0),
+        (Timestamp::from_date(2023, 2, 1), 30.0),
+    ]);
+    assert_eq!(piezometer.readings().len(), 2);
+    assert_relative_eq!(
+        piezometer
+            .pore_pressure_at(Timestamp::from_date(2023, 2, 1))
+            .unwrap(),
+        30.0
+    );
+    assert_eq!(
+        piezometer.pore_pressure_at(Timestamp::from_date(2022, 1, 1)),
+        None
+    );
+}
+
+#[rstest]
+#[case(0.0, 100.0)]
+#[case(2.0, 98.0)]
+#[case(5.0, 98.0)]
+#[case(20.0, 98.0)]
+fn heads_from_hydrostatic_pore_pressure(#[case] depth: f64, #[case] head: f64) {
+    let pore_pressure = ProfilePorePressure::hydrostatic(2.0, 20.0, 10.0);
+    let heads = HeadProfile::from_pore_pressure(&pore_pressure, Datum::new(100.0), 10.0);
+
+    assert_relative_eq!(heads.eval(depth), head);
+    assert_relative_eq!(heads.vertical_gradient(2.0, 20.0), 0.0);
+}
+
+#[test]
+fn heads_round_trip() {
+    let datum = Datum::new(45.0);
+    let pore_pressure = SteadySeepage::new(45.0, 35.0)
+        .with_unit_weight_water(9.81)
+        .solve(&underdrained_clay().with_datum(datum))
+        .unwrap();
+    let heads = HeadProfile::from_pore_pressure(&pore_pressure, datum, 9.81);
+    let back = heads.to_pore_pressure(datum, 9.81);
+
+    for (a, b) in pore_pressure.points().iter().zip(back.points()) {
+        assert_relative_eq!(a.x, b.x);
+        assert_relative_eq!(a.y, b.y, epsilon = 1e-9);
+    }
+}
+
+#[rstest]
+#[case(Extrapolation::Linear, Some(200.0))]
+#[case(Extrapolation::Clamp, None)]
+#[case(Extrapolation::Constant(50.0), None)]
+#[case(Extrapolation::Error, None)]
+fn heads_round_trip_out_of_range(
+    #[case] extrapolation: Extrapolation,
+    #[case] expected: Option<f64>,
+) {
+    let datum = Datum::new(100.0);
+    let pore_pressure =
+        ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 100.0)])
+            .with_extrapolation(extrapolation);
+    let heads = HeadProfile::from_pore_pressure(&pore_pressure, datum, 10.0);
+    let back = heads.to_pore_pressure(datum, 10.0);
+
+    assert_eq!(heads.try_eval(20.0).ok(), expected.map(|_| 100.0));
+    assert_eq!(back.try_eval(20.0).ok(), expected);
+}
+
+#[test]
+fn vertical_gradient() {
+    // Downward flow through the top layer, upward flow in the lower
+    let heads = HeadProfile::new(vec![
+        Point::new(0.0, 10.0),
+        Point::new(10.0, 5.0),
+        Point::new(20.0, 7.0),
+    ]);
+
+    assert_relative_eq!(heads.vertical_gradient(0.0, 10.0), 0.5);
+    assert_relative_eq!(heads.vertical_gradient(10.0, 20.0), -0.2);
+    assert_relative_eq!(heads.vertical_gradient(0.0, 20.0), 0.15);
+    assert_relative_eq!(heads.vertical_gradient_at(5.0), 0.5);
+    assert_relative_eq!(heads.vertical_gradient_at(15.0), -0.2);
+    assert_relative_eq!(
+        heads.to_pore_pressure(Datum::new(10.0), 10.0).eval(20.0),
+        170.0
+    );
+}
+
+#[rstest]
+#[case(Capillary::new(1.0), vec![(0.0, 0.0), (2.0, 0.0), (2.0, -10.0), (3.0, 0.0), (20.0, 170.0)])]
+#[case(Capillary::new(1.0).with_suction_limit(5.0), vec![(0.0, 0.0), (2.0, 0.0), (2.0, -5.0), (2.5, -5.0), (3.0, 0.0), (20.0, 170.0)])]
+#[case(Capillary::new(5.0), vec![(0.0, -30.0), (3.0, 0.0), (20.0, 170.0)])]
+#[case(Capillary::new(0.0), vec![(0.0, 0.0), (3.0, 0.0), (20.0, 170.0)])]
+fn capillary_zone(#[case] capillary: Capillary, #[case] expected: Vec<(f64, f64)>) {
+    let capillary = capillary.with_unit_weight_water(10.0);
+    let initial = ProfilePorePressure::hydrostatic(3.0, 20.0, 10.0);
+    let profile = capillary.apply(&initial);
+    let points = profile
+        .points()
+        .iter()
+        .map(|p| (p.x, p.y))
+        .collect::<Vec<_>>();
+
+    assert_relative_eq!(Capillary::water_table(&initial).unwrap(), 3.0);
+    assert_eq!(points, expected);
+}
+
+#[test]
+fn drawdown_with_capillary_zone() {
+    let initial = ProfilePorePressure::hydrostatic(2.0, 20.0, 10.0);
+    let drawdown = Drawdown::new(-50.0)
+        .with_shape(DrawdownShape::Uniform)
+        .with_capillary(Capillary::new(2.0).with_unit_weight_water(10.0));
+    let profile = initial.apply_drawdown(&drawdown);
+
+    assert_relative_eq!(profile.eval(7.0), 0.0);
+    assert_relative_eq!(profile.eval(6.0), -10.0);
+    assert_relative_eq!(profile.eval_left(5.0), 0.0);
+    assert_relative_eq!(profile.eval(5.0), -20.0);
+    assert_relative_eq!(profile.eval(2.0), 0.0);
+    assert_relative_eq!(profile.eval(20.0), 130.0);
+}
+
+fn clay_over_artesian_aquifer() -> (SoilProfile, ProfilePorePressure) {
+    let soil_profile = SoilProfile::default().with_soil_layers(vec![
+        SoilLayer::new(4.0, Box::new(Clay::default())),
+        SoilLayer::new(
+            6.0,
+            Box::new(Clay {
+                unit_weight: 20.0,
+                ..Default::default()
+            }),
+        ),
+    ]);
+    // Head 2 m above ground in the aquifer below 10 m
+    let pore_pressure = ProfilePorePressure::aquifers(&[Aquifer::new(10.0, 20.0, -2.0)], 10.0);
+    (soil_profile, pore_pressure)
+}
+
+#[rstest]
+#[case(2.0, vec![(4.0, 38.0, 48.0), (10.0, 158.0, 120.0)])]
+#[case(6.0, vec![(10.0, 80.0, 120.0)])]
+fn uplift_safety_factors(#[case] excavation: f64, #[case] expected: Vec<(f64, f64, f64)>) {
+    let (soil_profile, pore_pressure) = clay_over_artesian_aquifer();
+    let report = Uplift::new(excavation)
+        .check(&soil_profile, &pore_pressure)
+        .unwrap();
+
+    assert_eq!(report.interfaces.len(), expected.len());
+    for (interface, (depth, total_stress, u)) in report.interfaces.iter().zip(expected) {
+        assert_relative_eq!(interface.depth, depth);
+        assert_relative_eq!(interface.total_stress, total_stress);
+        assert_relative_eq!(interface.pore_pressure, u);
+        assert_relative_eq!(interface.safety_factor, total_stress / u);
+    }
+}
+
+#[test]
+fn uplift_required_drawdown() {
+    let (soil_profile, pore_pressure) = clay_over_artesian_aquifer();
+    let report = Uplift::new(6.0)
+        .with_target_safety_factor(1.2)
+        .with_unit_weight_water(10.0)
+        .check(&soil_profile, &pore_pressure)
+        .unwrap();
+
+    assert_relative_eq!(report.safety_factor(), 80.0 / 120.0);
+    assert_relative_eq!(report.governing().unwrap().depth, 10.0);
+    assert_relative_eq!(report.required_drawdown(), (120.0 - 80.0 / 1.2) / 10.0);
+
+    // Drawing the aquifer down by the required amount gives the target safety factor
+    let drawn_down = pore_pressure.apply_drawdown(
+        &Drawdown::new(-10.0 * report.required_drawdown()).with_shape(DrawdownShape::Uniform),
+    );
+    let after = Uplift::new(6.0).check(&soil_profile, &drawn_down).unwrap();
+    assert_relative_eq!(after.safety_factor(), 1.2, epsilon = 1e-12);
+}
+
+#[rstest]
+#[case(-1.0)]
+#[case(10.0)]
+fn uplift_invalid_excavation(#[case] excavation: f64) {
+    let (soil_profile, pore_pressure) = clay_over_artesian_aquifer();
+    assert_eq!(
+        Uplift::new(excavation)
+            .check(&soil_profile, &pore_pressure)
+            .unwrap_err(),
+        UpliftError::InvalidExcavationDepth {
+            depth: excavation,
+            bedrock: 10.0
+        }
+    );
+}
+
+#[rstest]
+#[case(0.5)]
+#[case(5.5)]
+#[case(14.5)]
+fn section_seepage_matches_layered_seepage(#[case] depth: f64) {
+    let soil_profile = underdrained_clay();
+    let section = SectionSeepage::new(4.0)
+        .with_cell_size(1.0)
+        .with_unit_weight_water(10.0)
+        .with_fixed_head(FixedHead::new(Edge::Top, 0.0, 4.0, 0.0))
+        .with_fixed_head(FixedHead::new(Edge::Bottom, 0.0, 4.0, -10.0))
+        .solve(&soil_profile)
+        .unwrap();
+    let layered = SteadySeepage::new(0.0, -10.0)
+        .with_unit_weight_water(10.0)
+        .solve(&soil_profile)
+        .unwrap();
+
+    assert_eq!(section.xs().len(), 4);
+    assert_eq!(section.depths().len(), 20);
+    for x in [0.5, 2.0, 3.5] {
+        assert_relative_eq!(
+            section.pore_pressure_profile(x).eval(depth),
+            layered.eval(depth),
+            epsilon = 1e-6
+        );
+    }
+    // Downward flow into the ground
+    assert!(section.max_exit_gradient().unwrap() < 0.0);
+}
+
+fn sheet_pile_wall(wall_bottom: f64) -> super::SeepageField {
+    let soil_profile = SoilProfile::default().with_soil_layers(vec![SoilLayer::new(
+        10.0,
+        Box::new(Clay {
+            permeability: Some(1e-5),
+            ..Default::default()
+        }),
+    )]);
+    SectionSeepage::new(20.0)
+        .with_cell_size(0.5)
+        .with_unit_weight_water(10.0)
+        .with_fixed_head(FixedHead::new(Edge::Top, 0.0, 10.0, 4.0))
+        .with_fixed_head(FixedHead::new(Edge::Top, 10.0, 20.0, 0.0))
+        .with_wall(Wall::new(10.0, 0.0, wall_bottom))
+        .solve(&soil_profile)
+        .unwrap()
+}
+
+#[test]
+fn section_seepage_under_wall() {
+    let field = sheet_pile_wall(5.0);
+    let heads = field.heads();
+
+    // Antisymmetric about the wall
+    for row in &heads {
+        for (left, right) in row.iter().zip(row.iter().rev()) {
+            assert_relative_eq!(left + right, 4.0, epsilon = 1e-6);
+        }
+    }
+    assert!(heads[0][19] > 3.0 && heads[0][20] < 1.0);
+    assert_relative_eq!(field.head(10.0, 9.75), 2.0, epsilon = 1e-6);
+    assert_relative_eq!(
+        field.pore_pressure(15.0, 6.0),
+        10.0 * (field.head(15.0, 6.0) + 6.0)
+    );
+    assert_relative_eq!(
+        field.pore_pressures()[3][7],
+        10.0 * (heads[3][7] + field.depths()[3])
+    );
+
+    // Upward flow on the downstream side, largest next to the wall, and less with a deeper wall
+    let exit = field.exit_gradients();
+    assert_eq!(exit.len(), 40);
+    assert!(exit[..20].iter().all(|p| p.y < 0.0));
+    assert!(exit[20..].iter().all(|p| p.y > 0.0));
+    assert_relative_eq!(field.max_exit_gradient().unwrap(), exit[20].y);
+    assert!(sheet_pile_wall(8.0).max_exit_gradient().unwrap() < exit[20].y);
+}
+
+#[test]
+fn section_seepage_errors() {
+    let soil_profile = underdrained_clay();
+    assert_eq!(
+        SectionSeepage::new(10.0).solve(&soil_profile).unwrap_err(),
+        SeepageError::NoFixedHead
+    );
+    assert_eq!(
+        SectionSeepage::new(0.0).solve(&soil_profile).unwrap_err(),
+        SeepageError::InvalidSection {
+            width: 0.0,
+            cell_size: 0.5
+        }
+    );
+}
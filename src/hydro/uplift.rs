@@ -0,0 +1,146 @@
+use std::fmt;
+
+use crate::hydro::{ProfilePorePressure, UNIT_WEIGHT_WATER};
+use crate::profile::Profile;
+use crate::soil::profile::SoilProfile;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum UpliftError {
+    /// The excavation reaches above the ground surface or down to bedrock
+    InvalidExcavationDepth { depth: f64, bedrock: f64 },
+}
+
+impl fmt::Display for UpliftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidExcavationDepth { depth, bedrock } => write!(
+                f,
+                "excavation depth {depth} is outside the soil profile down to bedrock at {bedrock}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UpliftError {}
+
+/// Uplift check at one layer interface below the excavation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UpliftInterface {
+    pub depth: f64,
+    /// Total vertical stress from the soil between the excavation bottom and the interface
+    pub total_stress: f64,
+    pub pore_pressure: f64,
+    /// Total stress over pore pressure, infinite where the pore pressure is not positive
+    pub safety_factor: f64,
+    /// Drawdown of the piezometric head in metres needed to reach the target safety factor
+    pub required_drawdown: f64,
+}
+
+/// Safety against hydraulic uplift of the soil plug below an excavation, for each layer
+/// interface between the excavation bottom and bedrock
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpliftReport {
+    pub interfaces: Vec<UpliftInterface>,
+}
+
+impl UpliftReport {
+    /// Interface with the lowest safety factor
+    #[must_use]
+    pub fn governing(&self) -> Option<&UpliftInterface> {
+        self.interfaces
+            .iter()
+            .min_by(|a, b| a.safety_factor.total_cmp(&b.safety_factor))
+    }
+    /// Lowest safety factor of all interfaces
+    #[must_use]
+    pub fn safety_factor(&self) -> f64 {
+        self.governing()
+            .map_or(f64::INFINITY, |interface| interface.safety_factor)
+    }
+    /// Drawdown of the piezometric head in metres needed to reach the target safety factor at
+    /// every interface
+    #[must_use]
+    pub fn required_drawdown(&self) -> f64 {
+        self.interfaces.iter().fold(0.0, |acc, interface| {
+            f64::max(acc, interface.required_drawdown)
+        })
+    }
+}
+
+/// Check of bottom heave from water pressure below an excavation to `excavation_depth`,
+/// weighing the total stress of the remaining soil against the pore pressure below it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Uplift {
+    pub excavation_depth: f64,
+    pub target_safety_factor: f64,
+    pub unit_weight_water: f64,
+}
+
+impl Uplift {
+    #[must_use]
+    pub fn new(excavation_depth: f64) -> Self {
+        Self {
+            excavation_depth,
+            target_safety_factor: 1.0,
+            unit_weight_water: UNIT_WEIGHT_WATER,
+        }
+    }
+    #[must_use]
+    pub fn with_target_safety_factor(mut self, target_safety_factor: f64) -> Self {
+        self.target_safety_factor = target_safety_factor;
+        self
+    }
+    #[must_use]
+    pub fn with_unit_weight_water(mut self, unit_weight_water: f64) -> Self {
+        self.unit_weight_water = unit_weight_water;
+        self
+    }
+
+    /// Safety factors at the layer interfaces below the excavation, with the pore pressure
+    /// below it from `pore_pressure`. At an interface with a jump in pore pressure, the value
+    /// below the interface is used
+    /// # Errors
+    /// Returns `UpliftError::InvalidExcavationDepth` if the excavation bottom is not within the
+    /// soil profile
+    pub fn check(
+        &self,
+        soil_profile: &SoilProfile,
+        pore_pressure: &ProfilePorePressure,
+    ) -> Result<UpliftReport, UpliftError> {
+        let depth = self.excavation_depth;
+        let bedrock = soil_profile.depth_to_bedrock();
+        if !(0.0..bedrock).contains(&depth) {
+            return Err(UpliftError::InvalidExcavationDepth { depth, bedrock });
+        }
+
+        let unit_weight = soil_profile.unit_weight_profile();
+        let interfaces = soil_profile
+            .layer_bounds()
+            .iter()
+            .map(|&(_, bottom, _)| bottom)
+            .filter(|&bottom| bottom > depth)
+            .map(|interface| {
+                let total_stress = unit_weight.integrate(depth, interface);
+                let u = pore_pressure.eval(interface);
+                let safety_factor = if u > 0.0 {
+                    total_stress / u
+                } else {
+                    f64::INFINITY
+                };
+                let required_drawdown = (u - total_stress / self.target_safety_factor).max(0.0)
+                    / self.unit_weight_water;
+
+                UpliftInterface {
+                    depth: interface,
+                    total_stress,
+                    pore_pressure: u,
+                    safety_factor,
+                    required_drawdown,
+                }
+            })
+            .collect();
+
+        Ok(UpliftReport { interfaces })
+    }
+}
@@ -0,0 +1,324 @@
+use std::f64::consts::PI;
+
+use crate::hydro::{
+    hydraulic_resistance, Drawdown, DrawdownShape, ProfilePorePressure, SeepageError,
+};
+use crate::profile::{DepthProfile, Point};
+use crate::soil::profile::SoilProfile;
+
+/// Default radius of a pumping well in metres
+const WELL_RADIUS: f64 = 0.1;
+
+/// Analytical solution for radial groundwater flow towards a pumping well. Lengths are in
+/// metres, times in seconds and discharges in m3/s
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RadialFlow {
+    /// Steady flow in a confined aquifer, with no drawdown at the radius of influence
+    Thiem {
+        transmissivity: f64,
+        radius_of_influence: f64,
+    },
+    /// Transient flow in a confined aquifer from the start of pumping
+    Theis {
+        transmissivity: f64,
+        storativity: f64,
+    },
+    /// Transient flow in a confined aquifer fed by leakage through an overlying aquitard without
+    /// storage (Hantush-Jacob). The leakage factor is sqrt(T c), where c is the hydraulic
+    /// resistance of the aquitard
+    HantushJacob {
+        transmissivity: f64,
+        storativity: f64,
+        leakage_factor: f64,
+    },
+    /// Steady flow in an unconfined aquifer resting on an impermeable base, with no drawdown at
+    /// the radius of influence
+    Dupuit {
+        permeability: f64,
+        saturated_thickness: f64,
+        radius_of_influence: f64,
+    },
+}
+
+impl RadialFlow {
+    /// Drawdown of the head in metres at `distance` from a well pumping `discharge`, after
+    /// pumping for `time`, evaluated no closer than the default well radius of [`Well::new`].
+    /// Dupuit drawdowns from several wells do not add up, see [`WellField::drawdown`]
+    #[must_use]
+    pub fn drawdown(&self, discharge: f64, distance: f64, time: f64) -> f64 {
+        self.drawdown_at(discharge, distance.max(WELL_RADIUS), time)
+    }
+
+    fn drawdown_at(&self, discharge: f64, distance: f64, time: f64) -> f64 {
+        match *self {
+            Self::Thiem {
+                transmissivity,
+                radius_of_influence,
+            } => discharge / (2.0 * PI * transmissivity) * log_ratio(radius_of_influence, distance),
+            Self::Theis {
+                transmissivity,
+                storativity,
+            } => {
+                if time <= 0.0 {
+                    return 0.0;
+                }
+                let u = distance * distance * storativity / (4.0 * transmissivity * time);
+                discharge / (4.0 * PI * transmissivity) * well_function(u)
+            }
+            Self::HantushJacob {
+                transmissivity,
+                storativity,
+                leakage_factor,
+            } => {
+                let u = if time > 0.0 {
+                    distance * distance * storativity / (4.0 * transmissivity * time)
+                } else {
+                    f64::INFINITY
+                };
+                discharge / (4.0 * PI * transmissivity)
+                    * leaky_well_function(u, distance / leakage_factor)
+            }
+            Self::Dupuit {
+                saturated_thickness,
+                ..
+            } => {
+                let h2 = saturated_thickness.powi(2) - self.potential(discharge, distance);
+                saturated_thickness - h2.max(0.0).sqrt()
+            }
+        }
+    }
+
+    /// Leaky aquifer below the soil layers of `soil_profile` from the ground surface down to
+    /// `aquifer_top`, which form the aquitard
+    /// # Errors
+    /// Returns a `SeepageError` if an aquitard layer lacks a valid permeability
+    pub fn hantush_jacob(
+        soil_profile: &SoilProfile,
+        aquifer_top: f64,
+        transmissivity: f64,
+        storativity: f64,
+    ) -> Result<Self, SeepageError> {
+        let resistance = hydraulic_resistance(soil_profile, 0.0, aquifer_top)?;
+        Ok(Self::HantushJacob {
+            transmissivity,
+            storativity,
+            leakage_factor: (transmissivity * resistance).sqrt(),
+        })
+    }
+
+    /// Reduction of the squared saturated thickness, which superposes for unconfined flow
+    fn potential(&self, discharge: f64, distance: f64) -> f64 {
+        match *self {
+            Self::Dupuit {
+                permeability,
+                radius_of_influence,
+                ..
+            } => discharge / (PI * permeability) * log_ratio(radius_of_influence, distance),
+            _ => 0.0,
+        }
+    }
+}
+
+/// ln(R / r) inside the radius of influence, zero outside it
+fn log_ratio(radius_of_influence: f64, distance: f64) -> f64 {
+    (radius_of_influence / distance).ln().max(0.0)
+}
+
+/// Theis well function W(u), the exponential integral E1(u), zero for infinite `u`
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn well_function(u: f64) -> f64 {
+    const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+    if u <= 0.0 {
+        return f64::INFINITY;
+    }
+    if u.is_infinite() {
+        return 0.0;
+    }
+
+    if u < 1.0 {
+        // Power series
+        let mut term = 1.0;
+        let mut sum = 0.0;
+        for k in 1..100 {
+            let k = f64::from(k);
+            term *= -u / k;
+            let next = -term / k;
+            sum += next;
+            if next.abs() < f64::EPSILON * sum.abs() {
+                break;
+            }
+        }
+        -EULER_GAMMA - u.ln() + sum
+    } else {
+        // Continued fraction, evaluated with the modified Lentz algorithm
+        let tiny = 1e-300;
+        let mut b = u + 1.0;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..100 {
+            let i = f64::from(i);
+            let a = -i * i;
+            b += 2.0;
+            d = 1.0 / (a * d + b);
+            c = b + a / c;
+            let delta = c * d;
+            h *= delta;
+            if (delta - 1.0).abs() < f64::EPSILON {
+                break;
+            }
+        }
+        h * (-u).exp()
+    }
+}
+
+/// Hantush-Jacob leaky well function W(u, r/B), integrated numerically. For `u` equal to zero
+/// it gives the steady-state value 2 K0(r/B)
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::many_single_char_names)]
+pub fn leaky_well_function(u: f64, rho: f64) -> f64 {
+    const INTERVALS: usize = 2000;
+    if rho <= 0.0 {
+        return well_function(u);
+    }
+    // With y = exp(s), W = integral of exp(-y - rho^2 / 4y) ds from ln(u), where the integrand
+    // is negligible outside exp(-50)
+    let b = rho * rho / 4.0;
+    let lower = u.ln().max((b / 50.0).ln());
+    let upper = 50.0_f64.ln();
+    if lower >= upper || u.is_infinite() {
+        return 0.0;
+    }
+    let h = (upper - lower) / INTERVALS as f64;
+    let f = |s: f64| {
+        let y = s.exp();
+        (-y - b / y).exp()
+    };
+
+    // Simpson's rule
+    (0..=INTERVALS).fold(0.0, |acc, i| {
+        let weight = match i {
+            0 => 1.0,
+            i if i == INTERVALS => 1.0,
+            i if i % 2 == 1 => 4.0,
+            _ => 2.0,
+        };
+        acc + weight * f(lower + h * i as f64)
+    }) * h
+        / 3.0
+}
+
+/// Pumping well, or an excavation treated as a well of equivalent radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Well {
+    /// Position in plan
+    pub position: Point,
+    /// Pumped discharge in m3/s
+    pub discharge: f64,
+    /// Drawdown is evaluated no closer to the centre than the well radius
+    pub radius: f64,
+}
+
+impl Well {
+    #[must_use]
+    pub fn new(position: Point, discharge: f64) -> Self {
+        Self {
+            position,
+            discharge,
+            radius: WELL_RADIUS,
+        }
+    }
+    /// Dewatered excavation of plan `area`, as a well of the same area
+    #[must_use]
+    pub fn excavation(position: Point, discharge: f64, area: f64) -> Self {
+        Self::new(position, discharge).with_radius((area / PI).sqrt())
+    }
+    #[must_use]
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+    #[must_use]
+    pub fn distance(&self, point: Point) -> f64 {
+        (point.x - self.position.x)
+            .hypot(point.y - self.position.y)
+            .max(self.radius)
+    }
+}
+
+/// Several wells pumping from the same aquifer
+#[derive(Debug, Clone, PartialEq)]
+pub struct WellField {
+    pub wells: Vec<Well>,
+    pub flow: RadialFlow,
+}
+
+impl WellField {
+    #[must_use]
+    pub fn new(wells: Vec<Well>, flow: RadialFlow) -> Self {
+        Self { wells, flow }
+    }
+
+    /// Drawdown of the head in metres at `point` in plan after pumping for `time`, superposing
+    /// the contribution of every well
+    #[must_use]
+    pub fn drawdown(&self, point: Point, time: f64) -> f64 {
+        match self.flow {
+            RadialFlow::Dupuit {
+                saturated_thickness,
+                ..
+            } => {
+                let potential = self.wells.iter().fold(0.0, |acc, well| {
+                    acc + self.flow.potential(well.discharge, well.distance(point))
+                });
+                let h2 = saturated_thickness.powi(2) - potential;
+                saturated_thickness - h2.max(0.0).sqrt()
+            }
+            _ => self.wells.iter().fold(0.0, |acc, well| {
+                acc + self
+                    .flow
+                    .drawdown_at(well.discharge, well.distance(point), time)
+            }),
+        }
+    }
+
+    /// Change in pore pressure in the aquifer at `point` in plan, negative for a reduction
+    #[must_use]
+    pub fn pore_pressure_change(&self, point: Point, time: f64, unit_weight_water: f64) -> f64 {
+        -unit_weight_water * self.drawdown(point, time)
+    }
+
+    /// Drawdown at `point` in plan for use with
+    /// [`ProfilePorePressure::apply_drawdown`](crate::hydro::ProfilePorePressure::apply_drawdown)
+    #[must_use]
+    pub fn boundary_drawdown(&self, point: Point, time: f64, unit_weight_water: f64) -> Drawdown {
+        Drawdown::new(self.pore_pressure_change(point, time, unit_weight_water))
+    }
+
+    /// Pore pressure in `soil_profile` at `point` in plan after pumping for `time`, with the
+    /// change of the aquifer from `aquifer_top` down to bedrock and a linear transition through
+    /// the aquitard above it, as for steady leakage
+    #[must_use]
+    pub fn leaky_pore_pressure_profile(
+        &self,
+        soil_profile: &SoilProfile,
+        aquifer_top: f64,
+        point: Point,
+        time: f64,
+        unit_weight_water: f64,
+    ) -> ProfilePorePressure {
+        let shape = DrawdownShape::Custom(DepthProfile::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(aquifer_top, 1.0),
+        ]));
+        let drawdown = self
+            .boundary_drawdown(point, time, unit_weight_water)
+            .with_shape(shape)
+            .with_bottom(soil_profile.depth_to_bedrock());
+
+        soil_profile
+            .pore_pressure_profile()
+            .apply_drawdown(&drawdown)
+    }
+}
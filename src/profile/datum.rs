@@ -0,0 +1,43 @@
+use crate::profile::point::Point;
+
+/// Vertical reference relating depth below ground to elevation, both in metres
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Datum {
+    pub ground_elevation: f64,
+}
+
+impl Datum {
+    #[must_use]
+    pub fn new(ground_elevation: f64) -> Self {
+        Self { ground_elevation }
+    }
+    #[must_use]
+    pub fn depth(&self, elevation: f64) -> f64 {
+        self.ground_elevation - elevation
+    }
+    #[must_use]
+    pub fn elevation(&self, depth: f64) -> f64 {
+        self.ground_elevation - depth
+    }
+    /// Convert points given as (elevation, value) to (depth, value)
+    #[must_use]
+    pub fn to_depth_points(&self, points: &[Point]) -> Vec<Point> {
+        points
+            .iter()
+            .map(|p| Point::new(self.depth(p.x), p.y))
+            .collect()
+    }
+    /// Convert points given as (depth, value) to (elevation, value)
+    #[must_use]
+    pub fn to_elevation_points(&self, points: &[Point]) -> Vec<Point> {
+        points
+            .iter()
+            .map(|p| Point::new(self.elevation(p.x), p.y))
+            .collect()
+    }
+    /// Depth below `other` of a point at zero depth below this datum
+    #[must_use]
+    pub fn offset_to(&self, other: &Datum) -> f64 {
+        other.ground_elevation - self.ground_elevation
+    }
+}
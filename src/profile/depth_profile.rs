@@ -0,0 +1,91 @@
+use crate::profile::impl_profile_ops;
+use crate::profile::Extrapolation;
+use crate::profile::Interpolation;
+use crate::profile::Point;
+use crate::profile::Profile;
+
+/// General depth dependent quantity, such as a stress, stiffness or soil parameter
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct DepthProfile {
+    points: Vec<Point>,
+    name: String,
+    unit: String,
+    extrapolation: Extrapolation,
+    interpolation: Interpolation,
+}
+
+impl DepthProfile {
+    #[must_use]
+    pub fn new(points: Vec<Point>) -> Self {
+        let mut copy = points;
+        copy.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
+        Self {
+            points: copy,
+            ..Default::default()
+        }
+    }
+    #[must_use]
+    pub fn with_name(mut self, name: &str) -> Self {
+        name.clone_into(&mut self.name);
+        self
+    }
+    #[must_use]
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        unit.clone_into(&mut self.unit);
+        self
+    }
+    #[must_use]
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    #[must_use]
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+impl Profile for DepthProfile {
+    fn new(points: Vec<Point>) -> Self {
+        DepthProfile::new(points)
+    }
+
+    fn points(&self) -> &Vec<Point> {
+        &self.points
+    }
+
+    fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
+    }
+
+    fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    fn with_extrapolation(self, extrapolation: Extrapolation) -> Self {
+        DepthProfile::with_extrapolation(self, extrapolation)
+    }
+
+    fn with_interpolation(self, interpolation: Interpolation) -> Self {
+        DepthProfile::with_interpolation(self, interpolation)
+    }
+
+    fn with_settings_from(self, source: &Self) -> Self {
+        self.with_name(&source.name)
+            .with_unit(&source.unit)
+            .with_extrapolation(source.extrapolation)
+            .with_interpolation(source.interpolation)
+    }
+}
+
+impl_profile_ops!(DepthProfile);
@@ -0,0 +1,36 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum ProfileError {
+    /// The profile has no points to evaluate
+    Empty,
+    /// A coordinate is NaN or infinite
+    NonFinite { x: f64, y: f64 },
+    /// A point lies above the ground surface where that is not allowed
+    NegativeDepth { x: f64 },
+    /// Two different values are given at the same depth without allowing discontinuities
+    DuplicateDepth { x: f64, values: (f64, f64) },
+    /// The evaluation point lies outside the defined range and the profile does not extrapolate
+    OutOfRange { x: f64, min: f64, max: f64 },
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "profile contains no points"),
+            Self::NonFinite { x, y } => write!(f, "point ({x}, {y}) is not finite"),
+            Self::NegativeDepth { x } => write!(f, "negative depth {x} is not allowed"),
+            Self::DuplicateDepth { x, values } => write!(
+                f,
+                "conflicting values {} and {} at depth {x}",
+                values.0, values.1
+            ),
+            Self::OutOfRange { x, min, max } => {
+                write!(f, "{x} is outside the profile range [{min}, {max}]")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
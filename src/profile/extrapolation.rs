@@ -0,0 +1,13 @@
+/// Policy for evaluating a profile outside the range covered by its points
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Extrapolation {
+    /// Use the value of the closest point
+    #[default]
+    Clamp,
+    /// Continue the first or last segment linearly
+    Linear,
+    /// Use a fixed value
+    Constant(f64),
+    /// Refuse to evaluate outside the range
+    Error,
+}
@@ -0,0 +1,151 @@
+use crate::profile::point::Point;
+
+/// Scheme used to evaluate a profile between two known points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Straight line between the points
+    #[default]
+    Linear,
+    /// Piecewise constant, holding the value of each point until the next point
+    Step,
+    /// Monotone piecewise cubic Hermite (PCHIP), never overshooting the data
+    MonotoneCubic,
+    /// Linear in the logarithm of the value, falls back to linear if a value is not positive
+    LogLinear,
+}
+
+impl Interpolation {
+    /// Evaluate the segment between `points[index - 1]` and `points[index]` at `eval_point`
+    #[must_use]
+    pub fn interpolate(self, points: &[Point], index: usize, eval_point: f64) -> f64 {
+        let p0 = points[index - 1];
+        let p1 = points[index];
+        let h = p1.x - p0.x;
+        if h == 0.0 {
+            return p0.y;
+        }
+        let t = (eval_point - p0.x) / h;
+
+        match self {
+            Self::LogLinear if p0.y > 0.0 && p1.y > 0.0 => p0.y * (p1.y / p0.y).powf(t),
+            Self::Linear | Self::LogLinear => p0.y + t * (p1.y - p0.y),
+            Self::Step => p0.y,
+            Self::MonotoneCubic => {
+                let d0 = pchip_slope(points, index - 1);
+                let d1 = pchip_slope(points, index);
+                let t2 = t * t;
+                let t3 = t2 * t;
+
+                (2.0 * t3 - 3.0 * t2 + 1.0) * p0.y
+                    + (t3 - 2.0 * t2 + t) * h * d0
+                    + (-2.0 * t3 + 3.0 * t2) * p1.y
+                    + (t3 - t2) * h * d1
+            }
+        }
+    }
+
+    /// Integrate the segment between `points[index - 1]` and `points[index]` from `a` to `b`,
+    /// where both limits lie within the segment
+    #[must_use]
+    pub fn integrate(self, points: &[Point], index: usize, a: f64, b: f64) -> f64 {
+        let p0 = points[index - 1];
+        let p1 = points[index];
+        let f = |x| self.interpolate(points, index, x);
+
+        match self {
+            Self::LogLinear
+                if p0.y > 0.0 && p1.y > 0.0 && (p1.y / p0.y).ln().abs() > f64::EPSILON =>
+            {
+                (f(b) - f(a)) * (p1.x - p0.x) / (p1.y / p0.y).ln()
+            }
+            Self::Linear | Self::LogLinear => (b - a) * (f(a) + f(b)) / 2.0,
+            Self::Step => (b - a) * p0.y,
+            // Simpson's rule is exact for cubics
+            Self::MonotoneCubic => (b - a) * (f(a) + 4.0 * f(f64::midpoint(a, b)) + f(b)) / 6.0,
+        }
+    }
+
+    /// Derivative of the segment between `points[index - 1]` and `points[index]` at `eval_point`
+    #[must_use]
+    pub fn derivative(self, points: &[Point], index: usize, eval_point: f64) -> f64 {
+        let p0 = points[index - 1];
+        let p1 = points[index];
+        let h = p1.x - p0.x;
+        if h == 0.0 {
+            return 0.0;
+        }
+
+        match self {
+            Self::LogLinear if p0.y > 0.0 && p1.y > 0.0 => {
+                self.interpolate(points, index, eval_point) * (p1.y / p0.y).ln() / h
+            }
+            Self::Linear | Self::LogLinear => (p1.y - p0.y) / h,
+            Self::Step => 0.0,
+            Self::MonotoneCubic => {
+                let d0 = pchip_slope(points, index - 1);
+                let d1 = pchip_slope(points, index);
+                let t = (eval_point - p0.x) / h;
+                let t2 = t * t;
+
+                ((6.0 * t2 - 6.0 * t) * p0.y
+                    + (3.0 * t2 - 4.0 * t + 1.0) * h * d0
+                    + (-6.0 * t2 + 6.0 * t) * p1.y
+                    + (3.0 * t2 - 2.0 * t) * h * d1)
+                    / h
+            }
+        }
+    }
+}
+
+/// Width and slope of the segment after `points[k]`, a vertical jump is given no slope
+fn secant(points: &[Point], k: usize) -> (f64, f64) {
+    let h = points[k + 1].x - points[k].x;
+    if h > 0.0 {
+        (h, (points[k + 1].y - points[k].y) / h)
+    } else {
+        (h, 0.0)
+    }
+}
+
+/// Fritsch-Carlson slope at `points[k]`, using the harmonic mean of the neighbouring secants
+/// in the interior and a shape preserving three point estimate at the ends
+fn pchip_slope(points: &[Point], k: usize) -> f64 {
+    let n = points.len();
+    if n < 3 {
+        return secant(points, 0).1;
+    }
+
+    if k == 0 || k == n - 1 {
+        let (h0, d0, h1, d1) = if k == 0 {
+            let (h0, d0) = secant(points, 0);
+            let (h1, d1) = secant(points, 1);
+            (h0, d0, h1, d1)
+        } else {
+            let (h0, d0) = secant(points, n - 2);
+            let (h1, d1) = secant(points, n - 3);
+            (h0, d0, h1, d1)
+        };
+        if h1 <= 0.0 {
+            return d0;
+        }
+        let slope = ((2.0 * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+
+        if slope.signum() != d0.signum() {
+            0.0
+        } else if d0.signum() != d1.signum() && slope.abs() > (3.0 * d0).abs() {
+            3.0 * d0
+        } else {
+            slope
+        }
+    } else {
+        let (h0, d0) = secant(points, k - 1);
+        let (h1, d1) = secant(points, k);
+        if d0 * d1 <= 0.0 {
+            return 0.0;
+        }
+        let w0 = 2.0 * h1 + h0;
+        let w1 = h1 + 2.0 * h0;
+
+        (w0 + w1) / (w0 / d0 + w1 / d1)
+    }
+}
@@ -1,7 +1,21 @@
+mod datum;
+mod depth_profile;
+mod error;
+mod extrapolation;
+mod interpolation;
+mod ops;
 mod point;
 mod profile_trait;
+mod validation;
+pub use datum::Datum;
+pub use depth_profile::DepthProfile;
+pub use error::ProfileError;
+pub use extrapolation::Extrapolation;
+pub use interpolation::Interpolation;
+pub(crate) use ops::impl_profile_ops;
 pub use point::Point;
 pub use profile_trait::Profile;
+pub use validation::{Discontinuity, Validation};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,87 @@
+/// Implement the arithmetic operators for a [`Profile`](crate::profile::Profile) implementor,
+/// in terms of [`Profile::zip_with`](crate::profile::Profile::zip_with) and
+/// [`Profile::map`](crate::profile::Profile::map)
+macro_rules! impl_profile_ops {
+    ($profile:ty) => {
+        impl std::ops::Add<Self> for $profile {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                &self + &rhs
+            }
+        }
+
+        impl std::ops::Add<&$profile> for &$profile {
+            type Output = $profile;
+
+            fn add(self, rhs: &$profile) -> Self::Output {
+                $crate::profile::Profile::zip_with(self, rhs, |a, b| a + b)
+            }
+        }
+
+        impl std::ops::Sub<Self> for $profile {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                &self - &rhs
+            }
+        }
+
+        impl std::ops::Sub<&$profile> for &$profile {
+            type Output = $profile;
+
+            fn sub(self, rhs: &$profile) -> Self::Output {
+                $crate::profile::Profile::zip_with(self, rhs, |a, b| a - b)
+            }
+        }
+
+        impl std::ops::Neg for $profile {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                -&self
+            }
+        }
+
+        impl std::ops::Neg for &$profile {
+            type Output = $profile;
+
+            fn neg(self) -> Self::Output {
+                $crate::profile::Profile::map(self, |y| -y)
+            }
+        }
+
+        impl std::ops::Mul<f64> for $profile {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self::Output {
+                &self * rhs
+            }
+        }
+
+        impl std::ops::Mul<f64> for &$profile {
+            type Output = $profile;
+
+            fn mul(self, rhs: f64) -> Self::Output {
+                $crate::profile::Profile::map(self, |y| y * rhs)
+            }
+        }
+
+        impl std::ops::Div<f64> for $profile {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self::Output {
+                &self / rhs
+            }
+        }
+
+        impl std::ops::Div<f64> for &$profile {
+            type Output = $profile;
+
+            fn div(self, rhs: f64) -> Self::Output {
+                $crate::profile::Profile::map(self, |y| y / rhs)
+            }
+        }
+    };
+}
+pub(crate) use impl_profile_ops;
@@ -1,41 +1,110 @@
+use crate::linspace;
+use crate::profile::datum::Datum;
+use crate::profile::error::ProfileError;
+use crate::profile::extrapolation::Extrapolation;
+use crate::profile::interpolation::Interpolation;
 use crate::profile::point::Point;
+use crate::profile::validation::Validation;
 
 pub trait Profile {
     // Associated function signature; `Self` refers to the implementor type.
     fn new(points: Vec<Point>) -> Self;
 
+    /// Construct a profile after checking the points with the default [`Validation`]
+    /// # Errors
+    /// See [`Validation::validate`]
+    fn try_new(points: Vec<Point>) -> Result<Self, ProfileError>
+    where
+        Self: Sized,
+    {
+        Self::try_new_with(points, Validation::default())
+    }
+
+    /// Construct a profile after checking the points with the given validation
+    /// # Errors
+    /// See [`Validation::validate`]
+    fn try_new_with(points: Vec<Point>, validation: Validation) -> Result<Self, ProfileError>
+    where
+        Self: Sized,
+    {
+        validation.validate(points).map(Self::new)
+    }
+
     fn points(&self) -> &Vec<Point>;
+    /// Behaviour outside the range covered by the points, clamps to the closest point by default
+    fn extrapolation(&self) -> Extrapolation {
+        Extrapolation::Clamp
+    }
+    /// Scheme used between the points, linear by default
+    fn interpolation(&self) -> Interpolation {
+        Interpolation::Linear
+    }
+    /// Profile with `extrapolation` outside its points, ignored by default for implementors
+    /// with a fixed behaviour
+    #[must_use]
+    fn with_extrapolation(self, _extrapolation: Extrapolation) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    /// Profile with `interpolation` between its points, ignored by default for implementors
+    /// with a fixed scheme
+    #[must_use]
+    fn with_interpolation(self, _interpolation: Interpolation) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    /// Profile with the extrapolation, interpolation and any other settings of `source`, used
+    /// for profiles derived from it
+    #[must_use]
+    fn with_settings_from(self, source: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.with_extrapolation(source.extrapolation())
+            .with_interpolation(source.interpolation())
+    }
     fn xs(&self) -> Vec<f64> {
         self.points().iter().map(|&p| p.x).collect::<Vec<f64>>()
     }
     fn ys(&self) -> Vec<f64> {
         self.points().iter().map(|&p| p.y).collect::<Vec<f64>>()
     }
+    /// Number of points at or before `eval_point`, found by binary search over the sorted points
+    fn partition_index(&self, eval_point: f64) -> usize {
+        self.points().partition_point(|p| p.x <= eval_point)
+    }
     fn point_below(&self, eval_point: f64) -> Option<&Point> {
-        self.points()
-            .iter()
-            .filter(|&p| p.x <= eval_point)
-            .collect::<Vec<&Point>>()
-            .last()
-            .copied()
+        self.partition_index(eval_point)
+            .checked_sub(1)
+            .map(|i| &self.points()[i])
     }
 
     fn point_above(&self, eval_point: f64) -> Option<&Point> {
-        self.points()
-            .iter()
-            .filter(|&p| p.x >= eval_point)
-            .collect::<Vec<&Point>>()
-            .first()
-            .copied()
+        let points = self.points();
+        points.get(points.partition_point(|p| p.x < eval_point))
     }
 
     fn point_around(&self, eval_point: f64) -> (Option<&Point>, Option<&Point>) {
         (self.point_below(eval_point), self.point_above(eval_point))
     }
 
+    /// Whether the profile jumps between two values at `eval_point`
+    fn is_discontinuous_at(&self, eval_point: f64) -> bool {
+        let points = self.points();
+        let index = points.partition_point(|p| p.x < eval_point);
+
+        index + 1 < points.len()
+            && points[index].x <= eval_point
+            && points[index + 1].x <= eval_point
+    }
+
     #[must_use]
     fn lerp(point_below: &Point, point_above: &Point, eval_point: f64) -> Point {
-        if point_below == point_above {
+        if point_below.x >= point_above.x {
             *point_below
         } else {
             let x0 = point_below.x;
@@ -46,20 +115,547 @@ pub trait Profile {
         }
     }
 
-    // Lerp Evaluate between two known points
-    fn eval(&self, eval_point: f64) -> f64 {
-        let point_around = self.point_around(eval_point);
+    /// Extrapolate from the end segment outside the range, according to the extrapolation policy
+    /// # Errors
+    /// Returns `ProfileError::OutOfRange` if the policy is `Extrapolation::Error`
+    fn extrapolate(&self, eval_point: f64, end: &Point, next: &Point) -> Result<f64, ProfileError> {
+        match self.extrapolation() {
+            Extrapolation::Clamp => Ok(end.y),
+            Extrapolation::Linear => {
+                if (next.x - end.x).abs() < f64::EPSILON {
+                    Ok(end.y)
+                } else {
+                    Ok(end.y + (eval_point - end.x) * (next.y - end.y) / (next.x - end.x))
+                }
+            }
+            Extrapolation::Constant(value) => Ok(value),
+            Extrapolation::Error => {
+                let xs = self.xs();
+                Err(ProfileError::OutOfRange {
+                    x: eval_point,
+                    min: xs[0],
+                    max: xs[xs.len() - 1],
+                })
+            }
+        }
+    }
+
+    /// Evaluate the profile, interpolating between known points and extrapolating outside them
+    /// # Errors
+    /// Returns `ProfileError::Empty` for a profile without points, and `ProfileError::OutOfRange`
+    /// outside the range if the extrapolation policy is `Extrapolation::Error`
+    fn try_eval(&self, eval_point: f64) -> Result<f64, ProfileError> {
+        self.try_eval_at(self.partition_index(eval_point), eval_point)
+    }
+
+    /// Evaluate the limit of the profile approaching `eval_point` from smaller values, which
+    /// differs from [`Profile::try_eval`] only at a vertical discontinuity
+    /// # Errors
+    /// See [`Profile::try_eval`]
+    fn try_eval_left(&self, eval_point: f64) -> Result<f64, ProfileError> {
+        let index = self.points().partition_point(|p| p.x < eval_point);
+        self.try_eval_at(index, eval_point)
+    }
 
-        match point_around {
+    /// Evaluate the limit from smaller values, see [`Profile::try_eval_left`]
+    /// # Panics
+    /// See [`Profile::eval`]
+    fn eval_left(&self, eval_point: f64) -> f64 {
+        match self.try_eval_left(eval_point) {
+            Ok(value) => value,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Evaluate the profile given the partition index of `eval_point`, see [`Profile::partition_index`]
+    /// # Errors
+    /// See [`Profile::try_eval`]
+    fn try_eval_at(&self, index: usize, eval_point: f64) -> Result<f64, ProfileError> {
+        let points = self.points();
+        let below = index.checked_sub(1).and_then(|i| points.get(i));
+
+        match (below, points.get(index)) {
             //case 1: x between two known points
-            (Some(point_below), Some(point_above)) => {
-                Self::lerp(point_below, point_above, eval_point).y
+            (Some(_), Some(_)) => Ok(self.interpolation().interpolate(points, index, eval_point)),
+            //case 2: x on the last point
+            (Some(point_below), None) if eval_point <= point_below.x => Ok(point_below.y),
+            //case 3: x above range
+            (Some(point_below), None) => self.extrapolate(
+                eval_point,
+                point_below,
+                &points[points.len().saturating_sub(2)],
+            ),
+            //case 4: x below range
+            (None, Some(point_above)) => {
+                self.extrapolate(eval_point, point_above, &points[1.min(points.len() - 1)])
+            }
+            (None, None) => Err(ProfileError::Empty),
+        }
+    }
+
+    /// Evaluate the profile, see [`Profile::try_eval`]
+    /// # Panics
+    /// Will panic if the profile is empty, or if `eval_point` is out of range and the
+    /// extrapolation policy is `Extrapolation::Error`
+    fn eval(&self, eval_point: f64) -> f64 {
+        match self.try_eval(eval_point) {
+            Ok(value) => value,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Evaluate the profile at several points in any order
+    /// # Panics
+    /// See [`Profile::eval`]
+    fn eval_many(&self, eval_points: &[f64]) -> Vec<f64> {
+        eval_points.iter().map(|&x| self.eval(x)).collect()
+    }
+
+    /// Evaluate the profile at points sorted in ascending order, walking the profile in a single pass
+    /// # Panics
+    /// Will panic if `eval_points` is not sorted, otherwise see [`Profile::eval`]
+    fn eval_sorted(&self, eval_points: &[f64]) -> Vec<f64> {
+        assert!(
+            eval_points.windows(2).all(|w| w[0] <= w[1]),
+            "evaluation points are not sorted"
+        );
+        let points = self.points();
+        let mut index = 0;
+
+        eval_points
+            .iter()
+            .map(|&x| {
+                while index < points.len() && points[index].x <= x {
+                    index += 1;
+                }
+                match self.try_eval_at(index, x) {
+                    Ok(value) => value,
+                    Err(err) => panic!("{err}"),
+                }
+            })
+            .collect()
+    }
+
+    /// Gradient of the profile at `eval_point`. On a point the segment after it is used,
+    /// and on the last point the segment before it
+    /// # Errors
+    /// See [`Profile::try_eval`]
+    fn try_gradient(&self, eval_point: f64) -> Result<f64, ProfileError> {
+        let points = self.points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Err(ProfileError::Empty);
+        };
+
+        if eval_point < first.x || eval_point > last.x {
+            // Clamped and constant extrapolation has no slope, linear continues the end segment
+            let value = self.try_eval(eval_point)?;
+            return Ok(match self.extrapolation() {
+                Extrapolation::Linear if eval_point < first.x && points.len() > 1 => {
+                    (value - first.y) / (eval_point - first.x)
+                }
+                Extrapolation::Linear if points.len() > 1 => {
+                    (value - last.y) / (eval_point - last.x)
+                }
+                _ => 0.0,
+            });
+        }
+        if points.len() < 2 {
+            return Ok(0.0);
+        }
+        let index = self.partition_index(eval_point).min(points.len() - 1);
+
+        Ok(self.interpolation().derivative(points, index, eval_point))
+    }
+
+    /// Gradient of the profile, see [`Profile::try_gradient`]
+    /// # Panics
+    /// See [`Profile::eval`]
+    fn gradient(&self, eval_point: f64) -> f64 {
+        match self.try_gradient(eval_point) {
+            Ok(value) => value,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Integral of the profile from `from` to `to`, exact for every interpolation scheme and
+    /// including the extrapolated parts outside the range
+    /// # Errors
+    /// See [`Profile::try_eval`]
+    fn try_integrate(&self, from: f64, to: f64) -> Result<f64, ProfileError> {
+        if to < from {
+            return self.try_integrate(to, from).map(|value| -value);
+        }
+        let points = self.points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Err(ProfileError::Empty);
+        };
+        let mut sum = 0.0;
+
+        // Extrapolation is constant or linear, where the midpoint rule is exact
+        if from < first.x {
+            let b = to.min(first.x);
+            sum += (b - from) * self.try_eval(f64::midpoint(from, b))?;
+        }
+        if to > last.x {
+            let a = from.max(last.x);
+            sum += (to - a) * self.try_eval(f64::midpoint(a, to))?;
+        }
+
+        let interpolation = self.interpolation();
+        let mut index = self.partition_index(from).max(1);
+        while index < points.len() && points[index - 1].x < to {
+            let a = from.max(points[index - 1].x);
+            let b = to.min(points[index].x);
+            if a < b {
+                sum += interpolation.integrate(points, index, a, b);
             }
-            //case 2: x below range closest value or extrapolate? -> Not implemented yet
-            (None, Some(point_above)) => point_above.y,
-            //case 3: x above range closest value or extrapolate? -> Not implemented yet
-            (Some(point_below), None) => point_below.y,
-            (None, None) => panic!("No points found"),
+            index += 1;
+        }
+
+        Ok(sum)
+    }
+
+    /// Integral of the profile, see [`Profile::try_integrate`]
+    /// # Panics
+    /// See [`Profile::eval`]
+    fn integrate(&self, from: f64, to: f64) -> f64 {
+        match self.try_integrate(from, to) {
+            Ok(value) => value,
+            Err(err) => panic!("{err}"),
         }
     }
+
+    /// Profile of the integral from the first point, exact at every point of the profile. The
+    /// integral of a step profile is interpolated linearly, which is exact between the points
+    #[must_use]
+    fn cumulative_integral(&self) -> Self
+    where
+        Self: Sized,
+    {
+        let points = self.points();
+        let interpolation = self.interpolation();
+        let mut sum = 0.0;
+
+        Self::new(
+            points
+                .iter()
+                .enumerate()
+                .map(|(index, point)| {
+                    if index > 0 {
+                        sum += interpolation.integrate(points, index, points[index - 1].x, point.x);
+                    }
+                    Point::new(point.x, sum)
+                })
+                .collect(),
+        )
+        .with_settings_from(self)
+        .with_interpolation(match interpolation {
+            Interpolation::Step => Interpolation::Linear,
+            interpolation => interpolation,
+        })
+    }
+
+    /// Profile with `f` applied to every value
+    #[must_use]
+    fn map(&self, f: impl Fn(f64) -> f64) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(
+            self.points()
+                .iter()
+                .map(|p| Point::new(p.x, f(p.y)))
+                .collect(),
+        )
+        .with_settings_from(self)
+    }
+
+    /// Combine two profiles point by point. The result has a point at every point of both
+    /// profiles, and a jump wherever either of them jumps, so combining piecewise linear
+    /// profiles is exact. Profiles with different interpolation give a linear result, where
+    /// the steps of a step profile are kept as jumps and curved segments are sampled at
+    /// intermediate points, so that only curved segments are approximated
+    /// # Panics
+    /// Will panic if either profile is empty, or if one of the profiles refuses to extrapolate
+    /// over the range of the other
+    #[must_use]
+    fn zip_with<P: Profile>(&self, other: &P, f: impl Fn(f64, f64) -> f64) -> Self
+    where
+        Self: Sized,
+    {
+        let mixed = self.interpolation() != other.interpolation();
+        let mut xs = self.xs();
+        xs.extend(other.xs());
+        if mixed {
+            xs.extend(curve_samples(self));
+            xs.extend(curve_samples(other));
+        }
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+
+        let mut points = Vec::with_capacity(xs.len());
+        for (x, (a, b)) in xs.iter().zip(
+            self.eval_sorted(&xs)
+                .into_iter()
+                .zip(other.eval_sorted(&xs)),
+        ) {
+            if jumps_at(self, *x, mixed) || jumps_at(other, *x, mixed) {
+                points.push(Point::new(*x, f(self.eval_left(*x), other.eval_left(*x))));
+            }
+            points.push(Point::new(*x, f(a, b)));
+        }
+
+        let result = Self::new(points).with_settings_from(self);
+        if mixed {
+            result.with_interpolation(Interpolation::Linear)
+        } else {
+            result
+        }
+    }
+
+    /// Profile evaluated on the points of `grid`, which is sorted first
+    /// # Panics
+    /// See [`Profile::eval`]
+    #[must_use]
+    fn resample(&self, grid: &[f64]) -> Self
+    where
+        Self: Sized,
+    {
+        let mut xs = grid.to_vec();
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+
+        Self::new(
+            xs.iter()
+                .zip(self.eval_sorted(&xs))
+                .map(|(&x, y)| Point::new(x, y))
+                .collect(),
+        )
+        .with_settings_from(self)
+    }
+
+    /// Profile evaluated on `n` evenly spaced points over the range of the profile
+    /// # Panics
+    /// Will panic if the profile is empty or `n` is less than 2
+    #[must_use]
+    fn resample_uniform(&self, n: usize) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(n >= 2, "at least two points are needed to resample");
+        let points = self.points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            panic!("{}", ProfileError::Empty);
+        };
+
+        self.resample(&linspace(first.x, last.x, n))
+    }
+
+    /// Remove points with the Douglas-Peucker algorithm, measuring the error in value at the
+    /// removed points with the interpolation of the profile. The simplified profile stays
+    /// within `tolerance` of the original at every removed point, and over its whole range for
+    /// linear and step interpolation. Both sides of a jump are always kept
+    #[must_use]
+    fn simplify(&self, tolerance: f64) -> Self
+    where
+        Self: Sized,
+    {
+        let points = self.points();
+        if points.len() < 3 {
+            return Self::new(points.clone()).with_settings_from(self);
+        }
+        let mut keep = vec![false; points.len()];
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+        for i in 1..points.len() {
+            if points[i].x <= points[i - 1].x {
+                keep[i - 1] = true;
+                keep[i] = true;
+            }
+        }
+
+        let mut stack = Vec::new();
+        let mut start = 0;
+        for (end, &keep) in keep.iter().enumerate().skip(1) {
+            if keep {
+                stack.push((start, end));
+                start = end;
+            }
+        }
+        let interpolation = self.interpolation();
+        while let Some((start, end)) = stack.pop() {
+            let segment = [points[start], points[end]];
+            let farthest = (start + 1..end)
+                .map(|i| {
+                    let value = interpolation.interpolate(&segment, 1, points[i].x);
+                    (i, (points[i].y - value).abs())
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((i, error)) = farthest {
+                if error > tolerance {
+                    keep[i] = true;
+                    stack.push((start, i));
+                    stack.push((i, end));
+                }
+            }
+        }
+
+        // Schemes that are not local to a segment, such as monotone cubic, may still miss
+        // removed points, which are then added back one at a time
+        loop {
+            let simplified = Self::new(
+                points
+                    .iter()
+                    .zip(&keep)
+                    .filter_map(|(&p, &keep)| keep.then_some(p))
+                    .collect(),
+            )
+            .with_settings_from(self);
+            let worst = points
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| !keep[i])
+                .map(|(i, p)| (i, (simplified.eval(p.x) - p.y).abs()))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            match worst {
+                Some((i, error)) if error > tolerance => keep[i] = true,
+                _ => return simplified,
+            }
+        }
+    }
+
+    /// Construct a profile from points given as (elevation, value) relative to `datum`
+    #[must_use]
+    fn from_elevations(points: &[Point], datum: Datum) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(datum.to_depth_points(points))
+    }
+
+    /// Points of the profile as (elevation, value), from the top down
+    fn elevation_points(&self, datum: Datum) -> Vec<Point> {
+        datum.to_elevation_points(self.points())
+    }
+
+    /// Evaluate the profile at an elevation relative to `datum`
+    /// # Panics
+    /// See [`Profile::eval`]
+    fn eval_elevation(&self, datum: Datum, elevation: f64) -> f64 {
+        self.eval(datum.depth(elevation))
+    }
+
+    /// Express a profile defined below `from` as depths below `to`, keeping every point at the
+    /// same elevation. Points above the new ground surface get negative depths
+    #[must_use]
+    fn rebase(&self, from: Datum, to: Datum) -> Self
+    where
+        Self: Sized,
+    {
+        let offset = from.offset_to(&to);
+        Self::new(
+            self.points()
+                .iter()
+                .map(|p| Point::new(p.x + offset, p.y))
+                .collect(),
+        )
+        .with_settings_from(self)
+    }
+
+    /// Depths where the piecewise linear profile through the points takes `value`, in
+    /// ascending order. A segment lying on `value` contributes its end points
+    fn crossings(&self, value: f64) -> Vec<f64> {
+        let points = self.points();
+        let mut res = points
+            .iter()
+            .filter(|p| (p.y - value).abs() <= f64::EPSILON * value.abs())
+            .map(|p| p.x)
+            .collect::<Vec<f64>>();
+
+        res.extend(points.windows(2).filter_map(|w| {
+            let (a, b) = (w[0].y - value, w[1].y - value);
+            if a * b < 0.0 {
+                Some(w[0].x + (w[1].x - w[0].x) * a / (a - b))
+            } else {
+                None
+            }
+        }));
+        res.sort_by(f64::total_cmp);
+        res.dedup();
+        res
+    }
+
+    /// Depths where the profile is zero, such as the phreatic surface of a pore pressure profile
+    fn zeros(&self) -> Vec<f64> {
+        self.crossings(0.0)
+    }
+
+    /// Depths where two profiles intersect, see [`Profile::crossings`]
+    /// # Panics
+    /// See [`Profile::zip_with`]
+    fn intersections<P: Profile>(&self, other: &P) -> Vec<f64>
+    where
+        Self: Sized,
+    {
+        self.zip_with(other, |a, b| a - b).zeros()
+    }
+
+    /// Point with the smallest value, the first one if several share it
+    fn min(&self) -> Option<Point> {
+        self.points()
+            .iter()
+            .copied()
+            .reduce(|acc, p| if p.y < acc.y { p } else { acc })
+    }
+
+    /// Point with the largest value, the first one if several share it
+    fn max(&self) -> Option<Point> {
+        self.points()
+            .iter()
+            .copied()
+            .reduce(|acc, p| if p.y > acc.y { p } else { acc })
+    }
+
+    /// Depth of the smallest value, see [`Profile::min`]
+    fn argmin(&self) -> Option<f64> {
+        self.min().map(|p| p.x)
+    }
+
+    /// Depth of the largest value, see [`Profile::max`]
+    fn argmax(&self) -> Option<f64> {
+        self.max().map(|p| p.x)
+    }
+}
+
+/// Whether `profile` jumps at `x` when expressed with linear interpolation, as at every inner
+/// point of a step profile
+fn jumps_at<P: Profile>(profile: &P, x: f64, as_linear: bool) -> bool {
+    let points = profile.points();
+    let index = points.partition_point(|p| p.x < x);
+
+    profile.is_discontinuous_at(x)
+        || (as_linear
+            && profile.interpolation() == Interpolation::Step
+            && index > 0
+            && points.get(index).is_some_and(|p| p.x <= x))
+}
+
+/// Intermediate points on the curved segments of `profile`, for combining it linearly
+#[allow(clippy::cast_precision_loss)]
+fn curve_samples<P: Profile>(profile: &P) -> Vec<f64> {
+    const SUBDIVISIONS: usize = 16;
+    match profile.interpolation() {
+        Interpolation::Linear | Interpolation::Step => Vec::new(),
+        Interpolation::MonotoneCubic | Interpolation::LogLinear => profile
+            .points()
+            .windows(2)
+            .filter(|w| w[1].x > w[0].x)
+            .flat_map(|w| {
+                (1..SUBDIVISIONS)
+                    .map(move |i| w[0].x + (w[1].x - w[0].x) * i as f64 / SUBDIVISIONS as f64)
+            })
+            .collect(),
+    }
 }
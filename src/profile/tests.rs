@@ -1 +1,592 @@
+use rstest::rstest;
 
+use crate::hydro::ProfilePorePressure;
+
+use super::{
+    Datum, DepthProfile, Discontinuity, Extrapolation, Interpolation, Point, Profile, ProfileError,
+    Validation,
+};
+
+fn hydrostatic() -> ProfilePorePressure {
+    ProfilePorePressure::new(vec![
+        Point::new(0.0, 0.0),
+        Point::new(5.0, 50.0),
+        Point::new(10.0, 100.0),
+    ])
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, 30.0, 100.0)]
+#[case(Extrapolation::Clamp, -5.0, 0.0)]
+#[case(Extrapolation::Linear, 30.0, 300.0)]
+#[case(Extrapolation::Linear, -5.0, -50.0)]
+#[case(Extrapolation::Constant(42.0), 30.0, 42.0)]
+#[case(Extrapolation::Constant(42.0), -5.0, 42.0)]
+#[case(Extrapolation::Error, 5.0, 50.0)]
+fn extrapolate(
+    #[case] extrapolation: Extrapolation,
+    #[case] eval_point: f64,
+    #[case] expected: f64,
+) {
+    let profile = hydrostatic().with_extrapolation(extrapolation);
+    approx::assert_abs_diff_eq!(profile.eval(eval_point), expected);
+}
+
+#[test]
+fn linear_extrapolation_single_point() {
+    let profile = ProfilePorePressure::new(vec![Point::new(2.0, 20.0)])
+        .with_extrapolation(Extrapolation::Linear);
+    approx::assert_abs_diff_eq!(profile.eval(10.0), 20.0);
+}
+
+#[test]
+fn try_eval_out_of_range() {
+    let profile = hydrostatic().with_extrapolation(Extrapolation::Error);
+    assert_eq!(
+        profile.try_eval(12.0),
+        Err(ProfileError::OutOfRange {
+            x: 12.0,
+            min: 0.0,
+            max: 10.0
+        })
+    );
+}
+
+#[test]
+fn try_eval_empty() {
+    let profile = ProfilePorePressure::new(vec![]);
+    assert_eq!(profile.try_eval(1.0), Err(ProfileError::Empty));
+}
+
+#[test]
+#[should_panic(expected = "profile contains no points")]
+fn eval_empty_panics() {
+    ProfilePorePressure::new(vec![]).eval(1.0);
+}
+
+#[rstest]
+#[case(-1.0, None, Some(0.0))]
+#[case(0.0, Some(0.0), Some(0.0))]
+#[case(2.5, Some(0.0), Some(5.0))]
+#[case(5.0, Some(5.0), Some(5.0))]
+#[case(11.0, Some(10.0), None)]
+fn point_around(#[case] eval_point: f64, #[case] below: Option<f64>, #[case] above: Option<f64>) {
+    let profile = hydrostatic();
+    let (point_below, point_above) = profile.point_around(eval_point);
+    assert_eq!(point_below.map(|p| p.x), below);
+    assert_eq!(point_above.map(|p| p.x), above);
+}
+
+#[test]
+fn eval_sorted_matches_eval() {
+    let profile = hydrostatic().with_extrapolation(Extrapolation::Linear);
+    let eval_points = crate::linspace(-2.0, 12.0, 29);
+    let expected = eval_points
+        .iter()
+        .map(|&x| profile.eval(x))
+        .collect::<Vec<f64>>();
+
+    assert_eq!(profile.eval_sorted(&eval_points), expected);
+    assert_eq!(profile.eval_many(&eval_points), expected);
+}
+
+#[test]
+fn eval_many_unsorted() {
+    let profile = hydrostatic();
+    assert_eq!(
+        profile.eval_many(&[7.5, 2.5, 10.0]),
+        vec![75.0, 25.0, 100.0]
+    );
+}
+
+#[test]
+#[should_panic(expected = "evaluation points are not sorted")]
+fn eval_sorted_unsorted_panics() {
+    hydrostatic().eval_sorted(&[7.5, 2.5]);
+}
+
+fn measured() -> Vec<Point> {
+    vec![
+        Point::new(0.0, 10.0),
+        Point::new(2.0, 20.0),
+        Point::new(4.0, 80.0),
+        Point::new(6.0, 90.0),
+    ]
+}
+
+#[rstest]
+#[case(Interpolation::Linear, 1.0, 15.0)]
+#[case(Interpolation::Linear, 3.0, 50.0)]
+#[case(Interpolation::Step, 1.0, 10.0)]
+#[case(Interpolation::Step, 3.9, 20.0)]
+#[case(Interpolation::Step, 4.0, 80.0)]
+#[case(Interpolation::LogLinear, 1.0, 200.0_f64.sqrt())]
+#[case(Interpolation::MonotoneCubic, 2.0, 20.0)]
+#[case(Interpolation::MonotoneCubic, 6.0, 90.0)]
+fn interpolate(
+    #[case] interpolation: Interpolation,
+    #[case] eval_point: f64,
+    #[case] expected: f64,
+) {
+    let profile = ProfilePorePressure::new(measured()).with_interpolation(interpolation);
+    approx::assert_relative_eq!(profile.eval(eval_point), expected);
+}
+
+#[test]
+fn log_linear_falls_back_to_linear() {
+    let profile = ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 100.0)])
+        .with_interpolation(Interpolation::LogLinear);
+    approx::assert_relative_eq!(profile.eval(5.0), 50.0);
+}
+
+#[test]
+fn monotone_cubic_does_not_overshoot() {
+    let profile =
+        ProfilePorePressure::new(measured()).with_interpolation(Interpolation::MonotoneCubic);
+    let values = profile.eval_sorted(&crate::linspace(0.0, 6.0, 61));
+
+    assert!(values.windows(2).all(|w| w[1] >= w[0]));
+    assert!(values.iter().all(|&v| (10.0..=90.0).contains(&v)));
+}
+
+#[test]
+fn default_interpolation_is_linear() {
+    assert_eq!(hydrostatic().interpolation(), Interpolation::Linear);
+}
+
+#[rstest]
+#[case(Interpolation::Linear, 0.0, 6.0, 30.0 + 100.0 + 170.0)]
+#[case(Interpolation::Linear, 6.0, 0.0, -(30.0 + 100.0 + 170.0))]
+#[case(Interpolation::Linear, 1.0, 3.0, 17.5 + 35.0)]
+#[case(Interpolation::Step, 1.0, 5.0, 10.0 + 40.0 + 80.0)]
+#[case(Interpolation::LogLinear, 0.0, 2.0, 20.0 / 2.0_f64.ln())]
+fn integrate(
+    #[case] interpolation: Interpolation,
+    #[case] from: f64,
+    #[case] to: f64,
+    #[case] expected: f64,
+) {
+    let profile = ProfilePorePressure::new(measured()).with_interpolation(interpolation);
+    approx::assert_relative_eq!(profile.integrate(from, to), expected, epsilon = 1e-9);
+}
+
+#[test]
+fn integrate_monotone_cubic_matches_riemann_sum() {
+    let profile =
+        ProfilePorePressure::new(measured()).with_interpolation(Interpolation::MonotoneCubic);
+    let n = 60_000;
+    let dx = 5.0 / f64::from(n);
+    let riemann = (0..n)
+        .map(|i| profile.eval(0.5 + dx * (f64::from(i) + 0.5)) * dx)
+        .sum::<f64>();
+
+    approx::assert_relative_eq!(profile.integrate(0.5, 5.5), riemann, epsilon = 1e-6);
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, 15.0, 10.0 * 100.0 / 2.0 + 5.0 * 100.0)]
+#[case(Extrapolation::Linear, 15.0, 15.0 * 150.0 / 2.0)]
+#[case(Extrapolation::Constant(0.0), 15.0, 10.0 * 100.0 / 2.0)]
+fn integrate_extrapolated(
+    #[case] extrapolation: Extrapolation,
+    #[case] to: f64,
+    #[case] expected: f64,
+) {
+    let profile = hydrostatic().with_extrapolation(extrapolation);
+    approx::assert_relative_eq!(profile.integrate(0.0, to), expected);
+}
+
+#[test]
+fn integrate_out_of_range() {
+    let profile = hydrostatic().with_extrapolation(Extrapolation::Error);
+    assert!(profile.try_integrate(0.0, 12.0).is_err());
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, 5.0, 10.0)]
+#[case(Extrapolation::Clamp, 10.0, 10.0)]
+#[case(Extrapolation::Clamp, 12.0, 0.0)]
+#[case(Extrapolation::Linear, 12.0, 10.0)]
+#[case(Extrapolation::Linear, -1.0, 10.0)]
+fn gradient(#[case] extrapolation: Extrapolation, #[case] eval_point: f64, #[case] expected: f64) {
+    let profile = hydrostatic().with_extrapolation(extrapolation);
+    approx::assert_relative_eq!(profile.gradient(eval_point), expected);
+}
+
+#[rstest]
+#[case(Interpolation::LogLinear)]
+#[case(Interpolation::MonotoneCubic)]
+fn gradient_matches_finite_difference(#[case] interpolation: Interpolation) {
+    let profile = ProfilePorePressure::new(measured()).with_interpolation(interpolation);
+    let h = 1e-6;
+    for x in [0.5, 2.5, 3.3, 5.0] {
+        let finite_difference = (profile.eval(x + h) - profile.eval(x - h)) / (2.0 * h);
+        approx::assert_relative_eq!(profile.gradient(x), finite_difference, epsilon = 1e-4);
+    }
+}
+
+#[test]
+fn cumulative_integral() {
+    let profile = ProfilePorePressure::new(measured()).cumulative_integral();
+    assert_eq!(profile.xs(), vec![0.0, 2.0, 4.0, 6.0]);
+    assert_eq!(profile.ys(), vec![0.0, 30.0, 130.0, 300.0]);
+}
+
+#[test]
+fn zip_with_keeps_all_breakpoints() {
+    let lhs = ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 100.0)]);
+    let rhs = ProfilePorePressure::new(vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(1.05, 0.5),
+        Point::new(10.0, 90.0),
+    ]);
+    let result = lhs.zip_with(&rhs, |a, b| a - b);
+
+    assert_eq!(result.xs(), vec![0.0, 1.0, 1.05, 10.0]);
+    approx::assert_relative_eq!(result.eval(1.05), 10.0);
+}
+
+#[rstest]
+#[case(5.0, 150.0)]
+#[case(12.0, 200.0)]
+fn add(#[case] eval_point: f64, #[case] expected: f64) {
+    let lhs = hydrostatic();
+    let rhs = ProfilePorePressure::new(vec![Point::new(5.0, 100.0), Point::new(12.0, 100.0)]);
+    approx::assert_relative_eq!((&lhs + &rhs).eval(eval_point), expected);
+    approx::assert_relative_eq!((lhs + rhs).eval(eval_point), expected);
+}
+
+#[test]
+fn scalar_operations() {
+    let profile = hydrostatic();
+    approx::assert_relative_eq!((-&profile).eval(5.0), -50.0);
+    approx::assert_relative_eq!((&profile * 1.5).eval(5.0), 75.0);
+    approx::assert_relative_eq!((profile / 2.0).eval(5.0), 25.0);
+}
+
+#[test]
+fn map() {
+    let profile = hydrostatic().map(|y| y + 1.0);
+    assert_eq!(profile.ys(), vec![1.0, 51.0, 101.0]);
+}
+
+#[test]
+fn zip_with_mixed_interpolation() {
+    let step = ProfilePorePressure::new(vec![Point::new(0.0, 1.0), Point::new(10.0, 2.0)])
+        .with_interpolation(Interpolation::Step);
+    let linear = ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 10.0)]);
+
+    for result in [&step + &linear, &linear + &step] {
+        assert_eq!(result.interpolation(), Interpolation::Linear);
+        approx::assert_relative_eq!(result.eval(5.0), 6.0);
+        approx::assert_relative_eq!(result.eval_left(10.0), 11.0);
+        approx::assert_relative_eq!(result.eval(10.0), 12.0);
+    }
+
+    let cubic =
+        ProfilePorePressure::new(measured()).with_interpolation(Interpolation::MonotoneCubic);
+    let zero = ProfilePorePressure::new(vec![Point::new(0.0, 0.0), Point::new(6.0, 0.0)]);
+    let result = &zero + &cubic;
+    for x in [1.0, 3.3, 5.1] {
+        approx::assert_relative_eq!(result.eval(x), cubic.eval(x), max_relative = 1e-2);
+    }
+}
+
+#[test]
+fn arithmetic_keeps_extrapolation() {
+    let profile = ProfilePorePressure::hydrostatic(0.0, 10.0, 10.0);
+    let zero = ProfilePorePressure::new(vec![Point::new(0.0, 0.0)]);
+    approx::assert_relative_eq!((&profile * 1.0).eval(30.0), 300.0);
+    approx::assert_relative_eq!((-&profile).eval(30.0), -300.0);
+    approx::assert_relative_eq!((&profile - &zero).eval(30.0), 300.0);
+    approx::assert_relative_eq!(profile.map(|y| y / 10.0).eval(30.0), 30.0);
+}
+
+#[test]
+fn arithmetic_keeps_interpolation() {
+    let profile = ProfilePorePressure::new(measured()).with_interpolation(Interpolation::Step);
+    let doubled = &profile * 2.0;
+    assert_eq!(doubled.interpolation(), Interpolation::Step);
+    approx::assert_relative_eq!(doubled.eval(3.9), 40.0);
+}
+
+#[test]
+fn arithmetic_keeps_name_and_unit() {
+    let profile = DepthProfile::new(vec![Point::new(0.0, 1.0), Point::new(10.0, 2.0)])
+        .with_name("Stiffness")
+        .with_unit("MPa")
+        .with_extrapolation(Extrapolation::Linear);
+    let result = &(&profile * 2.0) + &profile;
+    assert_eq!(result.name(), "Stiffness");
+    assert_eq!(result.unit(), "MPa");
+    approx::assert_relative_eq!(result.eval(20.0), 9.0);
+}
+
+#[rstest]
+#[case(vec![], ProfileError::Empty)]
+#[case(vec![Point::new(0.0, 0.0), Point::new(f64::NAN, 1.0)], ProfileError::NonFinite { x: f64::NAN, y: 1.0 })]
+#[case(vec![Point::new(-1.0, 0.0), Point::new(1.0, 1.0)], ProfileError::NegativeDepth { x: -1.0 })]
+#[case(vec![Point::new(2.0, 20.0), Point::new(0.0, 0.0), Point::new(2.0, 30.0)], ProfileError::DuplicateDepth { x: 2.0, values: (20.0, 30.0) })]
+fn try_new_rejects(#[case] points: Vec<Point>, #[case] expected: ProfileError) {
+    let err = ProfilePorePressure::try_new(points).unwrap_err();
+    // NaN never compares equal, so compare the variants through their messages
+    assert_eq!(err.to_string(), expected.to_string());
+}
+
+#[test]
+fn try_new_merges_identical_duplicates() {
+    let profile = ProfilePorePressure::try_new(vec![
+        Point::new(5.0, 50.0),
+        Point::new(0.0, 0.0),
+        Point::new(5.0, 50.0),
+    ])
+    .unwrap();
+    assert_eq!(profile.xs(), vec![0.0, 5.0]);
+}
+
+#[test]
+fn try_new_allows_negative_depth() {
+    let validation = Validation::default().with_negative_depth(true);
+    let profile =
+        ProfilePorePressure::try_new_with(vec![Point::new(-1.0, 0.0)], validation).unwrap();
+    assert_eq!(profile.xs(), vec![-1.0]);
+}
+
+fn aquitard_jump() -> ProfilePorePressure {
+    let validation = Validation::default().with_discontinuity(Discontinuity::Allow);
+    ProfilePorePressure::try_new_with(
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 50.0),
+            Point::new(5.0, 70.0),
+            Point::new(5.0, 80.0),
+            Point::new(10.0, 130.0),
+        ],
+        validation,
+    )
+    .unwrap()
+}
+
+#[test]
+fn discontinuity_keeps_first_and_last_value() {
+    assert_eq!(aquitard_jump().ys(), vec![0.0, 50.0, 80.0, 130.0]);
+}
+
+#[rstest]
+#[case(4.0, 40.0, 40.0)]
+#[case(5.0, 80.0, 50.0)]
+#[case(7.5, 105.0, 105.0)]
+fn discontinuity_eval(#[case] eval_point: f64, #[case] expected: f64, #[case] left: f64) {
+    let profile = aquitard_jump();
+    approx::assert_relative_eq!(profile.eval(eval_point), expected);
+    approx::assert_relative_eq!(profile.eval_left(eval_point), left);
+    assert_eq!(profile.is_discontinuous_at(eval_point), eval_point == 5.0);
+}
+
+#[test]
+fn discontinuity_integrate() {
+    let profile = aquitard_jump();
+    approx::assert_relative_eq!(profile.integrate(0.0, 10.0), 125.0 + 525.0);
+    approx::assert_relative_eq!(profile.gradient(5.0), 10.0);
+}
+
+#[test]
+fn discontinuity_zip_with() {
+    let result = aquitard_jump() - hydrostatic();
+    assert_eq!(result.xs(), vec![0.0, 5.0, 5.0, 10.0]);
+    assert_eq!(result.ys(), vec![0.0, 0.0, 30.0, 30.0]);
+}
+
+#[test]
+fn resample() {
+    let profile = hydrostatic().resample(&[7.5, 2.5, 12.0, 2.5]);
+    assert_eq!(profile.xs(), vec![2.5, 7.5, 12.0]);
+    assert_eq!(profile.ys(), vec![25.0, 75.0, 100.0]);
+}
+
+#[test]
+fn resample_uniform() {
+    let profile = ProfilePorePressure::new(measured()).resample_uniform(4);
+    assert_eq!(profile.xs(), vec![0.0, 2.0, 4.0, 6.0]);
+    assert_eq!(profile.ys(), vec![10.0, 20.0, 80.0, 90.0]);
+}
+
+#[test]
+fn resample_simplify_and_cumulative_integral_keep_policies() {
+    let profile = ProfilePorePressure::new(measured())
+        .with_interpolation(Interpolation::MonotoneCubic)
+        .with_extrapolation(Extrapolation::Linear);
+    for derived in [
+        profile.resample(&[0.0, 3.0, 6.0]),
+        profile.resample_uniform(7),
+        profile.simplify(1.0),
+        profile.cumulative_integral(),
+    ] {
+        assert_eq!(derived.interpolation(), Interpolation::MonotoneCubic);
+        assert_eq!(derived.extrapolation(), Extrapolation::Linear);
+    }
+
+    let step = ProfilePorePressure::new(measured()).with_interpolation(Interpolation::Step);
+    let integral = step.cumulative_integral();
+    assert_eq!(integral.interpolation(), Interpolation::Linear);
+    approx::assert_relative_eq!(integral.eval(3.0), step.integrate(0.0, 3.0));
+}
+
+#[rstest]
+#[case(0.0, vec![0.0, 2.0, 4.0, 6.0])]
+#[case(1.0, vec![0.0, 2.0, 4.0, 6.0])]
+#[case(20.0, vec![0.0, 6.0])]
+#[case(100.0, vec![0.0, 6.0])]
+fn simplify(#[case] tolerance: f64, #[case] expected: Vec<f64>) {
+    let profile = ProfilePorePressure::new(measured());
+    let simplified = profile.simplify(tolerance);
+
+    assert_eq!(simplified.xs(), expected);
+    for x in crate::linspace(0.0, 6.0, 61) {
+        assert!((simplified.eval(x) - profile.eval(x)).abs() <= tolerance + 1e-9);
+    }
+}
+
+#[rstest]
+#[case(0.1, vec![0.0, 2.0, 4.0, 6.0])]
+#[case(20.0, vec![0.0, 4.0, 6.0])]
+#[case(100.0, vec![0.0, 6.0])]
+fn simplify_step(#[case] tolerance: f64, #[case] expected: Vec<f64>) {
+    let profile = ProfilePorePressure::new(measured()).with_interpolation(Interpolation::Step);
+    let simplified = profile.simplify(tolerance);
+
+    assert_eq!(simplified.xs(), expected);
+    for x in crate::linspace(0.0, 6.0, 61) {
+        assert!((simplified.eval(x) - profile.eval(x)).abs() <= tolerance + 1e-9);
+    }
+}
+
+#[test]
+fn simplify_monotone_cubic_within_tolerance_at_points() {
+    let profile = ProfilePorePressure::new(
+        crate::linspace(0.0, 10.0, 21)
+            .into_iter()
+            .map(|x| Point::new(x, x.powi(3)))
+            .collect(),
+    )
+    .with_interpolation(Interpolation::MonotoneCubic);
+    let simplified = profile.simplify(1.0);
+
+    assert!(simplified.points().len() < profile.points().len());
+    for point in profile.points() {
+        assert!((simplified.eval(point.x) - point.y).abs() <= 1.0);
+    }
+}
+
+#[test]
+fn simplify_removes_collinear_points() {
+    let profile = hydrostatic().resample_uniform(101).simplify(1e-9);
+    assert_eq!(profile.xs(), vec![0.0, 10.0]);
+}
+
+#[test]
+fn simplify_keeps_jumps() {
+    let profile = aquitard_jump().simplify(1000.0);
+    assert_eq!(profile.ys(), vec![0.0, 50.0, 80.0, 130.0]);
+}
+
+#[test]
+fn from_elevations() {
+    let datum = Datum::new(12.0);
+    let profile = ProfilePorePressure::from_elevations(
+        &[Point::new(10.0, 0.0), Point::new(0.0, 100.0)],
+        datum,
+    );
+
+    assert_eq!(profile.xs(), vec![2.0, 12.0]);
+    approx::assert_relative_eq!(profile.eval_elevation(datum, 5.0), 50.0);
+    assert_eq!(
+        profile.elevation_points(datum),
+        vec![Point::new(10.0, 0.0), Point::new(0.0, 100.0)]
+    );
+}
+
+#[test]
+fn rebase_compares_at_same_elevation() {
+    let borehole_a = Datum::new(12.0);
+    let borehole_b = Datum::new(10.0);
+    let profile_a = hydrostatic();
+    let profile_b = profile_a.rebase(borehole_a, borehole_b);
+
+    assert_eq!(profile_b.xs(), vec![-2.0, 3.0, 8.0]);
+    for elevation in [9.0, 5.0, 2.5] {
+        approx::assert_relative_eq!(
+            profile_a.eval_elevation(borehole_a, elevation),
+            profile_b.eval_elevation(borehole_b, elevation)
+        );
+    }
+}
+
+#[test]
+fn rebase_keeps_policies() {
+    let profile = ProfilePorePressure::new(measured())
+        .with_interpolation(Interpolation::MonotoneCubic)
+        .with_extrapolation(Extrapolation::Linear);
+    let rebased = profile.rebase(Datum::new(12.0), Datum::new(10.0));
+
+    assert_eq!(rebased.interpolation(), Interpolation::MonotoneCubic);
+    assert_eq!(rebased.extrapolation(), Extrapolation::Linear);
+    approx::assert_relative_eq!(rebased.eval(8.0), profile.eval(10.0));
+}
+
+#[rstest]
+#[case(vec![Point::new(0.0, -20.0), Point::new(10.0, 80.0)], 0.0, vec![2.0])]
+#[case(vec![Point::new(0.0, -20.0), Point::new(2.0, 0.0), Point::new(10.0, 80.0)], 0.0, vec![2.0])]
+#[case(vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(10.0, 80.0)], 0.0, vec![0.0, 2.0])]
+#[case(vec![Point::new(0.0, 10.0), Point::new(5.0, 60.0), Point::new(10.0, 10.0)], 35.0, vec![2.5, 7.5])]
+#[case(vec![Point::new(0.0, 10.0), Point::new(10.0, 20.0)], 0.0, vec![])]
+fn crossings(#[case] points: Vec<Point>, #[case] value: f64, #[case] expected: Vec<f64>) {
+    let profile = ProfilePorePressure::new(points);
+    assert_eq!(profile.crossings(value), expected);
+}
+
+#[test]
+fn phreatic_surface() {
+    let profile = ProfilePorePressure::new(vec![Point::new(0.0, -15.0), Point::new(10.0, 85.0)]);
+    assert_eq!(profile.zeros(), vec![1.5]);
+}
+
+#[test]
+fn zero_on_jump() {
+    let validation = Validation::default().with_discontinuity(Discontinuity::Allow);
+    let profile = ProfilePorePressure::try_new_with(
+        vec![
+            Point::new(0.0, -10.0),
+            Point::new(5.0, -10.0),
+            Point::new(5.0, 10.0),
+        ],
+        validation,
+    )
+    .unwrap();
+    assert_eq!(profile.zeros(), vec![5.0]);
+}
+
+#[test]
+fn intersections() {
+    let initial = hydrostatic();
+    let drawdown = ProfilePorePressure::new(vec![Point::new(0.0, 20.0), Point::new(10.0, 60.0)]);
+    let result = initial.intersections(&drawdown);
+
+    assert_eq!(result.len(), 1);
+    approx::assert_relative_eq!(result[0], 20.0 / 6.0);
+}
+
+#[test]
+fn extrema() {
+    let profile = ProfilePorePressure::new(vec![
+        Point::new(0.0, 10.0),
+        Point::new(5.0, 60.0),
+        Point::new(10.0, -5.0),
+    ]);
+    assert_eq!(profile.max(), Some(Point::new(5.0, 60.0)));
+    assert_eq!(profile.min(), Some(Point::new(10.0, -5.0)));
+    assert_eq!(profile.argmax(), Some(5.0));
+    assert_eq!(profile.argmin(), Some(10.0));
+    assert_eq!(ProfilePorePressure::new(vec![]).argmax(), None);
+}
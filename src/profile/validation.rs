@@ -0,0 +1,76 @@
+use crate::profile::error::ProfileError;
+use crate::profile::point::Point;
+
+/// Policy for several points given at the same depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Discontinuity {
+    /// Points at the same depth must have the same value
+    #[default]
+    Reject,
+    /// Points at the same depth form a vertical jump from the first to the last value given.
+    /// The profile evaluates to the last value on the jump, see
+    /// [`Profile::eval_left`](crate::profile::Profile::eval_left) for the first
+    Allow,
+}
+
+/// Checks applied to the points of a profile before it is constructed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Validation {
+    pub discontinuity: Discontinuity,
+    pub allow_negative_depth: bool,
+}
+
+impl Validation {
+    #[must_use]
+    pub fn with_discontinuity(mut self, discontinuity: Discontinuity) -> Self {
+        self.discontinuity = discontinuity;
+        self
+    }
+    #[must_use]
+    pub fn with_negative_depth(mut self, allow_negative_depth: bool) -> Self {
+        self.allow_negative_depth = allow_negative_depth;
+        self
+    }
+
+    /// Sort the points and drop repeated points, keeping the first and last value of a jump
+    /// # Errors
+    /// Returns a `ProfileError` if the points are empty, not finite, negative when that is not
+    /// allowed, or give conflicting values at one depth when discontinuities are rejected
+    pub fn validate(self, points: Vec<Point>) -> Result<Vec<Point>, ProfileError> {
+        if points.is_empty() {
+            return Err(ProfileError::Empty);
+        }
+        if let Some(p) = points.iter().find(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return Err(ProfileError::NonFinite { x: p.x, y: p.y });
+        }
+        if let Some(p) = points
+            .iter()
+            .find(|p| p.x < 0.0 && !self.allow_negative_depth)
+        {
+            return Err(ProfileError::NegativeDepth { x: p.x });
+        }
+
+        let mut sorted = points;
+        sorted.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        let mut res: Vec<Point> = Vec::with_capacity(sorted.len());
+        for p in sorted {
+            let len = res.len();
+            match res.last() {
+                Some(last) if last.x < p.x => res.push(p),
+                Some(last) if (last.y - p.y).abs() <= f64::EPSILON * last.y.abs() => {}
+                Some(last) if self.discontinuity == Discontinuity::Reject => {
+                    return Err(ProfileError::DuplicateDepth {
+                        x: p.x,
+                        values: (last.y, p.y),
+                    });
+                }
+                // Replace the middle value of a jump given with more than two values
+                Some(_) if len > 1 && res[len - 2].x >= p.x => res[len - 1] = p,
+                _ => res.push(p),
+            }
+        }
+
+        Ok(res)
+    }
+}
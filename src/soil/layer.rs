@@ -14,4 +14,16 @@ impl SoilLayer {
             soil_model,
         }
     }
+    #[must_use]
+    pub fn permeability(&self) -> Option<f64> {
+        self.soil_model.permeability()
+    }
+    /// Preconsolidation stress at `depth` with the in-situ effective stress `p0`, equal to `p0`
+    /// for soil models without preconsolidation
+    #[must_use]
+    pub fn preconsolidation_stress(&self, depth: f64, p0: f64) -> f64 {
+        self.soil_model
+            .preconsolidation()
+            .map_or(p0, |preconsolidation| preconsolidation.stress(depth, p0))
+    }
 }
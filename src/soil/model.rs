@@ -1,4 +1,7 @@
 use std::fmt::Debug;
+
+use crate::profile::{DepthProfile, Profile};
+
 #[allow(clippy::module_name_repetitions)]
 pub trait SoilModel {
     fn unit_weight(&self) -> f64;
@@ -6,6 +9,28 @@ pub trait SoilModel {
         pd / self.elastic_modulus(p0, pd)
     }
     fn elastic_modulus(&self, p0: f64, pd: f64) -> f64;
+    /// Preconsolidation of the in-situ state, if the model has one
+    fn preconsolidation(&self) -> Option<&Preconsolidation> {
+        None
+    }
+    /// Strain for an increase `pd` from the in-situ effective stress `p0` with the
+    /// preconsolidation stress `pc`, see [`SoilProfile::pc`](crate::soil::profile::SoilProfile::pc)
+    fn compute_strain_with_pc(&self, p0: f64, _pc: f64, pd: f64) -> f64 {
+        self.compute_strain(p0, pd)
+    }
+    /// Modulus for an increase `pd` from the in-situ effective stress `p0` with the
+    /// preconsolidation stress `pc`
+    fn elastic_modulus_with_pc(&self, p0: f64, _pc: f64, pd: f64) -> f64 {
+        self.elastic_modulus(p0, pd)
+    }
+    /// Vertical hydraulic conductivity in m/s, if given
+    fn permeability(&self) -> Option<f64> {
+        None
+    }
+    /// Secondary compression, if the model creeps
+    fn creep(&self) -> Option<&Creep> {
+        None
+    }
 }
 impl Debug for dyn SoilModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -14,12 +39,16 @@ impl Debug for dyn SoilModel {
             .finish()
     }
 }
+/// Janbu's tangent modulus M = m σa ((σ' - σr) / σa)^(1 - a), with the modulus number `m`,
+/// the stress exponent `a`, the reference stress `p_ref` as σr and atmospheric pressure as σa
 #[allow(non_snake_case)]
 pub struct General {
     pub unit_weight: f64,
     pub p_ref: f64,
     pub m: f64,
     pub a: f64,
+    pub permeability: Option<f64>,
+    pub creep: Option<Creep>,
 }
 
 impl Default for General {
@@ -29,51 +58,161 @@ impl Default for General {
             m: 20.0,
             a: 1.0,
             p_ref: 0.0,
+            permeability: None,
+            creep: None,
         }
     }
 }
+impl General {
+    /// Atmospheric pressure in kPa, σa
+    pub const SIGMA_A: f64 = 100.0;
+    /// Smallest stress above `p_ref` in kPa for a = 0, where the modulus is proportional to it
+    pub const MIN_STRESS: f64 = 1.0;
+
+    /// Tangent modulus at the effective stress `sigma`. Zero at and below `p_ref` for 0 < a < 1,
+    /// and taken at `MIN_STRESS` above `p_ref` for a = 0
+    #[must_use]
+    pub fn tangent_modulus(&self, sigma: f64) -> f64 {
+        self.m * Self::SIGMA_A * self.normalised_stress(sigma).powf(1.0 - self.a)
+    }
+    /// (σ' - σr) / σa, not below `MIN_STRESS` for a = 0 and not below zero otherwise
+    fn normalised_stress(&self, sigma: f64) -> f64 {
+        let floor = if self.a.abs() < f64::EPSILON {
+            Self::MIN_STRESS / Self::SIGMA_A
+        } else {
+            0.0
+        };
+        ((sigma - self.p_ref) / Self::SIGMA_A).max(floor)
+    }
+}
 impl SoilModel for General {
     fn unit_weight(&self) -> f64 {
         self.unit_weight
     }
-    #[allow(clippy::cast_precision_loss)]
+    fn permeability(&self) -> Option<f64> {
+        self.permeability
+    }
+    fn creep(&self) -> Option<&Creep> {
+        self.creep.as_ref()
+    }
+    /// Exact integral of 1 / M from `p0` to `p0 + pd`. No strain develops below `p_ref` for
+    /// a > 0, or below `MIN_STRESS` above it for a = 0
+    fn compute_strain(&self, p0: f64, pd: f64) -> f64 {
+        let s0 = self.normalised_stress(p0);
+        let s1 = self.normalised_stress(p0 + pd);
+
+        if self.a.abs() < f64::EPSILON {
+            (s1 / s0).ln() / self.m
+        } else if (self.a - 1.0).abs() < f64::EPSILON {
+            pd / (self.m * Self::SIGMA_A)
+        } else {
+            (s1.powf(self.a) - s0.powf(self.a)) / (self.m * self.a)
+        }
+    }
+    /// Secant modulus over the stress increment, the tangent modulus for no increment
     fn elastic_modulus(&self, p0: f64, pd: f64) -> f64 {
-        const LOAD_STEPS: usize = 10;
-        const SIGMA_REF: f64 = 100.0;
-        let dp = pd / (LOAD_STEPS - 1) as f64;
+        if pd.abs() < 1e-9 {
+            return self.tangent_modulus(p0);
+        }
+        pd / self.compute_strain(p0, pd)
+    }
+}
+/// Secondary compression, with the creep strain growing with the logarithm of time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Creep {
+    /// Secondary compression index Cα with the void ratio e0, ε = Cα / (1 + e0) log10(t / tr)
+    SecondaryCompression { c_alpha: f64, void_ratio: f64 },
+    /// Janbu's time resistance number rs, ε = ln(t / tr) / rs
+    TimeResistance(f64),
+    /// Janbu's creep number αs = 1 / rs, ε = αs ln(t / tr)
+    CreepNumber(f64),
+}
+
+impl Creep {
+    /// Creep strain at `elapsed_time` counted from `reference_time`, zero before it
+    #[must_use]
+    pub fn strain(&self, reference_time: f64, elapsed_time: f64) -> f64 {
+        if elapsed_time <= reference_time || reference_time <= 0.0 {
+            return 0.0;
+        }
+        let log = (elapsed_time / reference_time).ln();
+        match *self {
+            Self::SecondaryCompression {
+                c_alpha,
+                void_ratio,
+            } => c_alpha / (1.0 + void_ratio) * log / std::f64::consts::LN_10,
+            Self::TimeResistance(r_s) => log / r_s,
+            Self::CreepNumber(alpha_s) => alpha_s * log,
+        }
+    }
+}
+
+/// Preconsolidation stress as a property of the in-situ state
+#[derive(Debug, Clone)]
+pub enum Preconsolidation {
+    /// Overconsolidation ratio, pc = OCR σ'0 for the in-situ effective stress σ'0
+    Ocr(f64),
+    /// Pre-overburden pressure in kPa, pc = σ'0 + POP
+    Pop(f64),
+    /// Preconsolidation stress against depth below ground
+    Profile(DepthProfile),
+}
+
+impl Default for Preconsolidation {
+    fn default() -> Self {
+        Self::Ocr(1.0)
+    }
+}
 
-        (0..LOAD_STEPS).fold(0.0, |acc, i| {
-            let sigma_m = p0 + dp * (0.5 + i as f64);
-            acc + self.m * SIGMA_REF * (sigma_m / SIGMA_REF).powf(1.0 - self.a)
-        }) / (LOAD_STEPS as f64)
+impl Preconsolidation {
+    /// Preconsolidation stress at `depth` with the in-situ effective stress `p0`
+    #[must_use]
+    pub fn stress(&self, depth: f64, p0: f64) -> f64 {
+        match self {
+            Self::Ocr(ratio) => ratio * p0,
+            Self::Pop(pop) => p0 + pop,
+            Self::Profile(profile) => profile.eval(depth),
+        }
+    }
+    /// Preconsolidation stress where the depth is unknown. An explicit profile can not be
+    /// evaluated then, and the soil is taken as normally consolidated
+    fn stress_at_unknown_depth(&self, p0: f64) -> f64 {
+        match self {
+            Self::Profile(_) => p0,
+            _ => self.stress(0.0, p0),
+        }
     }
 }
+
 #[derive(Debug)]
 #[allow(non_snake_case)]
 pub struct Clay {
     pub unit_weight: f64,
-    pub over_consolidation_ratio: f64,
+    pub preconsolidation: Preconsolidation,
     pub M: f64,
     pub m: f64,
     pub p_ref: f64,
+    pub permeability: Option<f64>,
+    pub creep: Option<Creep>,
 }
 
 impl Default for Clay {
     fn default() -> Self {
         Self {
             unit_weight: 19.0,
-            over_consolidation_ratio: 1.0,
+            preconsolidation: Preconsolidation::default(),
             M: 5000.0,
             m: 20.0,
             p_ref: 0.0,
+            permeability: None,
+            creep: None,
         }
     }
 }
 impl Clay {
-    /// # TODO
-    /// 1. Quality control this shit
+    /// Preconsolidation stress for the in-situ effective stress `p_0`
     fn pc(&self, p_0: f64) -> f64 {
-        self.over_consolidation_ratio * p_0
+        self.preconsolidation.stress_at_unknown_depth(p_0)
     }
     /// # TODO
     /// 1. Quality control this shit
@@ -92,13 +231,42 @@ impl SoilModel for Clay {
         self.unit_weight
     }
 
-    /// # TODO
-    /// 1. Quality control this shit
+    fn preconsolidation(&self) -> Option<&Preconsolidation> {
+        Some(&self.preconsolidation)
+    }
+
+    fn permeability(&self) -> Option<f64> {
+        self.permeability
+    }
+    fn creep(&self) -> Option<&Creep> {
+        self.creep.as_ref()
+    }
+
     fn elastic_modulus(&self, p0: f64, pd: f64) -> f64 {
-        let pc = self.pc(p0);
+        self.elastic_modulus_with_pc(p0, self.pc(p0), pd)
+    }
+
+    fn compute_strain_with_pc(&self, p0: f64, pc: f64, pd: f64) -> f64 {
         if pd < 0.001 {
+            return pd / self.M;
+        }
+        pd / self.elastic_modulus_with_pc(p0, pc, pd)
+    }
+
+    /// # TODO
+    /// 1. Quality control this shit
+    fn elastic_modulus_with_pc(&self, p0: f64, pc: f64, pd: f64) -> f64 {
+        if pd < 0.0 {
             return self.M;
         }
+        if pd < 0.001 {
+            // Tangent modulus at p0
+            return if p0 < pc {
+                self.stiffness_overconsolidated()
+            } else {
+                self.stiffness_normalconsolidated(p0, 0.0)
+            };
+        }
         if (p0 + pd) < pc {
             // Pure overconsolidated behaviour
             self.stiffness_overconsolidated()
@@ -117,6 +285,116 @@ impl SoilModel for Clay {
         }
     }
 }
+/// Clay with Janbu's three zones for the tangent oedometer modulus: the constant `M0` in the
+/// overconsolidated range up to the preconsolidation stress pc, the constant `ML` from pc up to
+/// σ'L, and `m (σ' - σr)` in the normally consolidated range beyond σ'L. Unloading follows `M0`
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct JanbuClay {
+    pub unit_weight: f64,
+    pub preconsolidation: Preconsolidation,
+    /// Modulus in the overconsolidated range in kPa
+    pub M0: f64,
+    /// Modulus just past the preconsolidation stress in kPa
+    pub ML: f64,
+    /// Effective stress σ'L in kPa where the normally consolidated range starts, where
+    /// `m (σ' - σr)` reaches `ML` if not given or not above σr. Never below pc
+    pub sigma_l: Option<f64>,
+    /// Modulus number in the normally consolidated range
+    pub m: f64,
+    /// Intercept stress σr in kPa where the normally consolidated modulus is zero
+    pub sigma_r: f64,
+    pub permeability: Option<f64>,
+    pub creep: Option<Creep>,
+}
+
+impl Default for JanbuClay {
+    fn default() -> Self {
+        Self {
+            unit_weight: 19.0,
+            preconsolidation: Preconsolidation::default(),
+            M0: 5000.0,
+            ML: 1000.0,
+            sigma_l: None,
+            m: 20.0,
+            sigma_r: 0.0,
+            permeability: None,
+            creep: None,
+        }
+    }
+}
+
+impl JanbuClay {
+    /// Stress at the start of the normally consolidated range for the preconsolidation `pc`.
+    /// A given σ'L at or below σr is replaced by the default
+    #[must_use]
+    pub fn sigma_l(&self, pc: f64) -> f64 {
+        self.sigma_l
+            .filter(|&sigma_l| sigma_l > self.sigma_r)
+            .unwrap_or(self.sigma_r + self.ML / self.m)
+            .max(pc)
+    }
+    /// Tangent modulus on the loading curve at the effective stress `sigma`
+    #[must_use]
+    pub fn tangent_modulus(&self, sigma: f64, pc: f64) -> f64 {
+        if sigma < pc {
+            self.M0
+        } else if sigma < self.sigma_l(pc) {
+            self.ML
+        } else {
+            self.m * (sigma - self.sigma_r)
+        }
+    }
+}
+
+impl SoilModel for JanbuClay {
+    fn unit_weight(&self) -> f64 {
+        self.unit_weight
+    }
+    fn preconsolidation(&self) -> Option<&Preconsolidation> {
+        Some(&self.preconsolidation)
+    }
+    fn permeability(&self) -> Option<f64> {
+        self.permeability
+    }
+    fn creep(&self) -> Option<&Creep> {
+        self.creep.as_ref()
+    }
+    fn compute_strain(&self, p0: f64, pd: f64) -> f64 {
+        self.compute_strain_with_pc(p0, self.preconsolidation.stress_at_unknown_depth(p0), pd)
+    }
+    fn elastic_modulus(&self, p0: f64, pd: f64) -> f64 {
+        self.elastic_modulus_with_pc(p0, self.preconsolidation.stress_at_unknown_depth(p0), pd)
+    }
+    /// Exact integral of 1 / M over the parts of the stress increment in each zone
+    fn compute_strain_with_pc(&self, p0: f64, pc: f64, pd: f64) -> f64 {
+        if pd <= 0.0 {
+            return pd / self.M0;
+        }
+        let sigma_l = self.sigma_l(pc);
+        let p1 = p0 + pd;
+        let within = |from: f64, to: f64| (p1.min(to) - p0.max(from)).max(0.0);
+
+        let overconsolidated = within(f64::NEG_INFINITY, pc) / self.M0;
+        let transition = within(pc, sigma_l) / self.ML;
+        let normally_consolidated = if p1 > sigma_l {
+            let start = p0.max(sigma_l);
+            ((p1 - self.sigma_r) / (start - self.sigma_r)).ln() / self.m
+        } else {
+            0.0
+        };
+
+        overconsolidated + transition + normally_consolidated
+    }
+    /// Secant modulus over the stress increment, the tangent modulus for no increment
+    fn elastic_modulus_with_pc(&self, p0: f64, pc: f64, pd: f64) -> f64 {
+        if pd.abs() < 1e-9 {
+            return self.tangent_modulus(p0, pc);
+        }
+        pd / self.compute_strain_with_pc(p0, pc, pd)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
@@ -129,13 +407,190 @@ mod tests {
         dbg!(clay);
     }
 
+    #[rstest::rstest]
+    #[case(0.0, 0.0, (150.0_f64 / 100.0).ln() / 20.0)]
+    #[case(0.0, 50.0, (100.0_f64 / 50.0).ln() / 20.0)]
+    #[case(0.5, 0.0, (1.5_f64.sqrt() - 1.0) / 10.0)]
+    #[case(1.0, 0.0, 50.0 / 2000.0)]
+    #[case(1.0, 50.0, 50.0 / 2000.0)]
+    fn janbu_strain(#[case] a: f64, #[case] p_ref: f64, #[case] expected: f64) {
+        let soil = General {
+            a,
+            p_ref,
+            ..Default::default()
+        };
+        assert_relative_eq!(soil.compute_strain(100.0, 50.0), expected, epsilon = 1e-15);
+        assert_relative_eq!(
+            soil.compute_strain(150.0, -50.0),
+            -expected,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(soil.elastic_modulus(100.0, 50.0), 50.0 / expected);
+    }
+
+    #[rstest::rstest]
+    #[case(0.0)]
+    #[case(0.3)]
+    #[case(0.5)]
+    #[case(1.0)]
+    fn janbu_strain_matches_tangent_modulus(#[case] a: f64) {
+        let soil = General {
+            a,
+            p_ref: 20.0,
+            ..Default::default()
+        };
+        // Midpoint rule over many small steps
+        let steps = 10_000;
+        let dp = 80.0 / f64::from(steps);
+        let numerical = (0..steps).fold(0.0, |acc, i| {
+            acc + dp / soil.tangent_modulus(40.0 + dp * (f64::from(i) + 0.5))
+        });
+
+        assert_relative_eq!(
+            soil.compute_strain(40.0, 80.0),
+            numerical,
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(soil.elastic_modulus(40.0, 0.0), soil.tangent_modulus(40.0));
+    }
+
+    #[test]
+    fn janbu_strain_below_reference_stress() {
+        let soil = General {
+            a: 0.0,
+            p_ref: 20.0,
+            ..Default::default()
+        };
+        assert_relative_eq!(soil.compute_strain(10.0, 30.0), 20.0_f64.ln() / 20.0);
+        assert_relative_eq!(soil.compute_strain(5.0, 10.0), 0.0);
+        assert_relative_eq!(soil.tangent_modulus(10.0), 20.0 * General::MIN_STRESS);
+    }
+
+    fn janbu_clay() -> JanbuClay {
+        JanbuClay {
+            M0: 6000.0,
+            ML: 1500.0,
+            sigma_l: Some(150.0),
+            m: 15.0,
+            sigma_r: 50.0,
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    // Within one zone
+    #[case(50.0, 30.0, 30.0 / 6000.0)]
+    #[case(110.0, 30.0, 30.0 / 1500.0)]
+    #[case(200.0, 50.0, (200.0_f64 / 150.0).ln() / 15.0)]
+    // Across zone boundaries
+    #[case(80.0, 40.0, 20.0 / 6000.0 + 20.0 / 1500.0)]
+    #[case(80.0, 120.0, 20.0 / 6000.0 + 50.0 / 1500.0 + 1.5_f64.ln() / 15.0)]
+    // Unloading
+    #[case(200.0, -50.0, -50.0 / 6000.0)]
+    fn janbu_clay_strain(#[case] p0: f64, #[case] pd: f64, #[case] expected: f64) {
+        let clay = janbu_clay();
+        assert_relative_eq!(clay.compute_strain_with_pc(p0, 100.0, pd), expected);
+        if pd.abs() > 0.0 {
+            assert_relative_eq!(clay.elastic_modulus_with_pc(p0, 100.0, pd), pd / expected);
+        }
+    }
+
+    #[test]
+    fn janbu_clay_matches_tangent_modulus() {
+        let clay = janbu_clay();
+        let steps = 100_000;
+        let dp = 200.0 / f64::from(steps);
+        let numerical = (0..steps).fold(0.0, |acc, i| {
+            acc + dp / clay.tangent_modulus(60.0 + dp * (f64::from(i) + 0.5), 100.0)
+        });
+
+        assert_relative_eq!(
+            clay.compute_strain_with_pc(60.0, 100.0, 200.0),
+            numerical,
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(clay.elastic_modulus_with_pc(120.0, 100.0, 0.0), 1500.0);
+    }
+
+    #[test]
+    fn janbu_clay_zone_boundaries() {
+        let clay = JanbuClay {
+            preconsolidation: Preconsolidation::Ocr(1.5),
+            ..Default::default()
+        };
+        // Normally consolidated from where m (σ' - σr) reaches ML, but never below pc
+        assert_relative_eq!(clay.sigma_l(20.0), 50.0);
+        assert_relative_eq!(clay.sigma_l(80.0), 80.0);
+        assert_relative_eq!(
+            clay.compute_strain(40.0, 20.0),
+            clay.compute_strain_with_pc(40.0, 60.0, 20.0)
+        );
+        assert_relative_eq!(clay.tangent_modulus(100.0, 60.0), 2000.0);
+    }
+
+    #[rstest::rstest]
+    #[case(10.0)]
+    #[case(50.0)]
+    fn janbu_clay_sigma_l_above_sigma_r(#[case] sigma_l: f64) {
+        let clay = JanbuClay {
+            sigma_l: Some(sigma_l),
+            ..janbu_clay()
+        };
+        assert_relative_eq!(clay.sigma_l(20.0), 50.0 + 1500.0 / 15.0);
+        for (p0, pd) in [(40.0, 200.0), (200.0, 50.0)] {
+            let strain = clay.compute_strain_with_pc(p0, 20.0, pd);
+            assert!(strain.is_finite() && strain > 0.0);
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(Creep::SecondaryCompression { c_alpha: 0.02, void_ratio: 1.0 }, 0.01)]
+    #[case(Creep::TimeResistance(200.0), 10.0_f64.ln() / 200.0)]
+    #[case(Creep::CreepNumber(0.005), 0.005 * 10.0_f64.ln())]
+    fn creep_strain(#[case] creep: Creep, #[case] expected: f64) {
+        assert_relative_eq!(creep.strain(1.0, 10.0), expected);
+        assert_relative_eq!(creep.strain(10.0, 100.0), expected);
+        assert_relative_eq!(creep.strain(10.0, 5.0), 0.0);
+    }
+
     #[test]
     fn over_consolidation_ratio() {
         let clay = Clay {
-            over_consolidation_ratio: 1.2,
+            preconsolidation: Preconsolidation::Ocr(1.2),
             ..Default::default()
         };
-        assert_relative_eq!(clay.over_consolidation_ratio, clay.pc(1.0));
+        assert_relative_eq!(1.2, clay.pc(1.0));
+    }
+
+    #[rstest::rstest]
+    #[case(Preconsolidation::Ocr(1.5), 150.0)]
+    #[case(Preconsolidation::Pop(30.0), 130.0)]
+    #[case(Preconsolidation::Profile(DepthProfile::new(vec![crate::profile::Point::new(0.0, 50.0), crate::profile::Point::new(10.0, 250.0)])), 150.0)]
+    fn preconsolidation_stress(#[case] preconsolidation: Preconsolidation, #[case] expected: f64) {
+        assert_relative_eq!(preconsolidation.stress(5.0, 100.0), expected);
+    }
+
+    #[test]
+    fn clay_stiffness_uses_given_pc() {
+        let clay = Clay::default();
+        // Overconsolidated up to pc, normally consolidated beyond
+        assert_relative_eq!(clay.elastic_modulus_with_pc(100.0, 150.0, 40.0), clay.M);
+        assert_relative_eq!(
+            clay.elastic_modulus_with_pc(100.0, 100.0, 40.0),
+            clay.m * 120.0
+        );
+        assert_relative_eq!(
+            clay.compute_strain_with_pc(100.0, 150.0, 40.0),
+            40.0 / clay.M
+        );
+        assert_relative_eq!(
+            clay.elastic_modulus(100.0, 40.0),
+            clay.elastic_modulus_with_pc(100.0, 100.0, 40.0)
+        );
+        // Tangent at no increment
+        assert_relative_eq!(clay.elastic_modulus_with_pc(90.0, 90.0, 0.0), clay.m * 90.0);
+        assert_relative_eq!(clay.elastic_modulus_with_pc(90.0, 150.0, 0.0), clay.M);
+        assert_relative_eq!(clay.elastic_modulus_with_pc(90.0, 90.0, -10.0), clay.M);
     }
 
     // #[test]
@@ -1,6 +1,49 @@
-use crate::{delta, hydro::ProfilePorePressure, linspace, profile::Profile};
+use std::fmt;
+
+use crate::{
+    delta,
+    hydro::{ProfilePorePressure, Suction},
+    linspace,
+    profile::{Datum, DepthProfile, Interpolation, Point, Profile},
+};
 
 use super::layer::SoilLayer;
+use super::model::{Preconsolidation, SoilModel};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum SoilProfileError {
+    /// The layer at this index does not start where the layer above it, or the ground surface
+    /// for the first layer, ends
+    LayerGap {
+        layer: usize,
+        expected_top: f64,
+        top: f64,
+    },
+    /// The layer at this index does not have its bottom below its top
+    InvalidLayer { layer: usize, top: f64, bottom: f64 },
+}
+
+impl fmt::Display for SoilProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LayerGap {
+                layer,
+                expected_top,
+                top,
+            } => write!(
+                f,
+                "soil layer {layer} starts at elevation {top} instead of {expected_top}"
+            ),
+            Self::InvalidLayer { layer, top, bottom } => write!(
+                f,
+                "soil layer {layer} has its bottom at elevation {bottom}, not below its top {top}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SoilProfileError {}
 
 #[derive(Debug)]
 #[allow(clippy::module_name_repetitions)]
@@ -9,6 +52,8 @@ pub struct SoilProfile {
     soil_layers: Vec<SoilLayer>,
     pore_pressure_profile: ProfilePorePressure,
     manual_bedrock_depth: Option<f64>,
+    datum: Datum,
+    suction: Suction,
 }
 impl SoilProfile {
     #[must_use]
@@ -16,6 +61,35 @@ impl SoilProfile {
         self.soil_layers = soil_layers;
         self
     }
+    /// Set the soil layers from (top, bottom, soil model) given as elevations relative to the
+    /// datum of the profile, which should be set first. The layers must follow each other
+    /// from the ground surface down
+    /// # Errors
+    /// Returns a `SoilProfileError` if a layer does not start where the one above it ends, or
+    /// does not have its bottom below its top
+    pub fn with_soil_layers_at_elevations(
+        self,
+        layers: Vec<(f64, f64, Box<dyn SoilModel>)>,
+    ) -> Result<Self, SoilProfileError> {
+        let mut expected_top = self.datum.ground_elevation;
+        let mut soil_layers = Vec::with_capacity(layers.len());
+        for (layer, (top, bottom, soil_model)) in layers.into_iter().enumerate() {
+            if (top - expected_top).abs() > 1e-9 {
+                return Err(SoilProfileError::LayerGap {
+                    layer,
+                    expected_top,
+                    top,
+                });
+            }
+            if bottom >= top {
+                return Err(SoilProfileError::InvalidLayer { layer, top, bottom });
+            }
+            soil_layers.push(SoilLayer::new(top - bottom, soil_model));
+            expected_top = bottom;
+        }
+
+        Ok(self.with_soil_layers(soil_layers))
+    }
     #[must_use]
     pub fn with_pore_pressure_profile(
         mut self,
@@ -32,6 +106,36 @@ impl SoilProfile {
     pub fn set_depth_to_bedrock(&mut self, manual_bedrock_depth: f64) {
         self.manual_bedrock_depth = Some(manual_bedrock_depth);
     }
+    #[must_use]
+    pub fn with_datum(mut self, datum: Datum) -> Self {
+        self.datum = datum;
+        self
+    }
+    /// Whether negative pore pressure counts towards the effective stress, including in the
+    /// settlement calculation
+    #[must_use]
+    pub fn with_suction(mut self, suction: Suction) -> Self {
+        self.suction = suction;
+        self
+    }
+    /// Set the bedrock depth from its elevation, relative to the datum of the profile
+    #[must_use]
+    pub fn with_bedrock_elevation(self, bedrock_elevation: f64) -> Self {
+        let depth = self.datum.depth(bedrock_elevation);
+        self.with_depth_to_bedrock(depth)
+    }
+    #[must_use]
+    pub fn datum(&self) -> Datum {
+        self.datum
+    }
+    #[must_use]
+    pub fn pore_pressure_profile(&self) -> &ProfilePorePressure {
+        &self.pore_pressure_profile
+    }
+    #[must_use]
+    pub fn suction(&self) -> Suction {
+        self.suction
+    }
 }
 impl SoilProfile {
     #[must_use]
@@ -47,43 +151,27 @@ impl SoilProfile {
     #[must_use]
     pub fn in_situ_effective_stress(&self, depth: f64) -> Option<f64> {
         let total_stress_at_depth = self.in_situ_total_stress(depth);
-        let pore_pressure_at_depth = self.pore_pressure_profile.eval(depth);
+        let pore_pressure_at_depth = self
+            .suction
+            .effective_pore_pressure(self.pore_pressure_profile.eval(depth));
 
         total_stress_at_depth.map(|sigma| sigma - pore_pressure_at_depth)
     }
+    /// Preconsolidation stress at `depth` from the in-situ state, equal to the in-situ
+    /// effective stress for soil models without preconsolidation
     #[must_use]
     pub fn pc(&self, depth: f64) -> Option<f64> {
-        let _x = self.get_soil_layer(depth)?;
-        todo!()
+        let layer = self.get_soil_layer(depth)?;
+        let p0 = self.in_situ_effective_stress(depth)?;
+
+        Some(layer.preconsolidation_stress(depth, p0))
     }
     #[must_use]
     pub fn in_situ_total_stress(&self, depth: f64) -> Option<f64> {
-        if depth < 0.0 {
+        if depth < 0.0 || depth > self.depth_to_bedrock() {
             return None;
         }
-
-        if depth > self.depth_to_bedrock() {
-            None
-        } else {
-            let mut sum = 0.0;
-            let mut z = 0.0;
-            let mut layercount = 0;
-
-            while z < depth {
-                let soil_layer = &self.soil_layers[layercount];
-
-                if z + soil_layer.thickness < depth {
-                    z += soil_layer.thickness;
-                    sum += soil_layer.thickness * soil_layer.soil_model.unit_weight();
-                } else {
-                    let dz = depth - z;
-                    sum += dz * soil_layer.soil_model.unit_weight();
-                    z = depth;
-                }
-                layercount += 1;
-            }
-            Some(sum)
-        }
+        self.unit_weight_profile().try_integrate(0.0, depth).ok()
     }
 
     /// # Panics
@@ -119,22 +207,239 @@ impl SoilProfile {
     /// Idunno dude
     #[must_use]
     pub fn compute_settlement(&self, drawdown: &ProfilePorePressure) -> f64 {
+        self.integrate_strain(drawdown, |layer, p0, pc, pd| {
+            layer.soil_model.compute_strain_with_pc(p0, pc, pd)
+        })
+    }
+
+    /// Settlement from creep at `elapsed_time` after the change to `drawdown`, counted from
+    /// `reference_time`, such as the end of primary consolidation. Only sublayers where the
+    /// effective stress increases creep, with the creep parameters of their soil models
+    /// # Panics
+    /// See [`SoilProfile::compute_settlement`]
+    #[must_use]
+    pub fn creep_settlement(
+        &self,
+        drawdown: &ProfilePorePressure,
+        reference_time: f64,
+        elapsed_time: f64,
+    ) -> f64 {
+        self.integrate_strain(drawdown, |layer, _, _, pd| match layer.soil_model.creep() {
+            Some(creep) if pd > 0.0 => creep.strain(reference_time, elapsed_time),
+            _ => 0.0,
+        })
+    }
+
+    /// Primary consolidation settlement with creep settlement added, see
+    /// [`SoilProfile::creep_settlement`]
+    /// # Panics
+    /// See [`SoilProfile::compute_settlement`]
+    #[must_use]
+    pub fn compute_settlement_with_creep(
+        &self,
+        drawdown: &ProfilePorePressure,
+        reference_time: f64,
+        elapsed_time: f64,
+    ) -> f64 {
+        self.compute_settlement(drawdown)
+            + self.creep_settlement(drawdown, reference_time, elapsed_time)
+    }
+
+    /// Sum of the strain in each sublayer times its thickness, from the in-situ effective
+    /// stress, preconsolidation stress and increase in effective stress of the sublayer
+    fn integrate_strain(
+        &self,
+        drawdown: &ProfilePorePressure,
+        strain: impl Fn(&SoilLayer, f64, f64, f64) -> f64,
+    ) -> f64 {
         const n: usize = 100;
         let delta = delta(0.0, self.depth_to_bedrock(), n);
 
-        linspace(0.0, self.depth_to_bedrock() - delta, n)
+        let eval_depths = linspace(0.0, self.depth_to_bedrock() - delta, n)
             .iter()
-            .fold(0.0, |acc, &z| {
-                let eval_depth = z + delta / 2.0;
-                let p0 = self.in_situ_effective_stress(eval_depth).unwrap();
-                let pd = self.pore_pressure_profile.eval(eval_depth) - drawdown.eval(eval_depth);
-
-                let strain = self
-                    .get_soil_layer(eval_depth)
-                    .unwrap()
-                    .soil_model
-                    .compute_strain(p0, pd);
-                acc + strain * delta
+            .map(|z| z + delta / 2.0)
+            .collect::<Vec<f64>>();
+        let sigma = self.total_stress_profile().eval_sorted(&eval_depths);
+        let u0 = self.pore_pressure_profile.eval_sorted(&eval_depths);
+        let u1 = drawdown.eval_sorted(&eval_depths);
+
+        eval_depths.iter().zip(sigma).zip(u0).zip(u1).fold(
+            0.0,
+            |acc, (((&eval_depth, sigma), u0), u1)| {
+                let u0 = self.suction.effective_pore_pressure(u0);
+                let u1 = self.suction.effective_pore_pressure(u1);
+                let p0 = sigma - u0;
+                let pd = u0 - u1;
+
+                let layer = self.get_soil_layer(eval_depth).unwrap();
+                let pc = layer.preconsolidation_stress(eval_depth, p0);
+                acc + strain(layer, p0, pc, pd) * delta
+            },
+        )
+    }
+}
+impl SoilProfile {
+    /// Top and bottom of every layer above bedrock
+    #[must_use]
+    pub fn layer_bounds(&self) -> Vec<(f64, f64, &SoilLayer)> {
+        let bedrock = self.depth_to_bedrock();
+        let mut top = 0.0;
+
+        self.soil_layers
+            .iter()
+            .map_while(|layer| {
+                if top >= bedrock {
+                    return None;
+                }
+                let bottom: f64 = top + layer.thickness;
+                let bounds = (top, bottom.min(bedrock), layer);
+                top = bottom;
+                Some(bounds)
             })
+            .collect()
+    }
+
+    /// Depths of the points of the pore pressure profile and where it changes sign, between
+    /// which the effective stress is linear also when suction is ignored
+    fn pore_pressure_depths(&self) -> Vec<f64> {
+        let mut depths = self.pore_pressure_profile.xs();
+        depths.extend(self.pore_pressure_profile.zeros());
+        depths.sort_by(f64::total_cmp);
+        depths.dedup();
+        depths
+    }
+
+    /// Sample a layer dependent quantity at the top and bottom of every layer and at every
+    /// point of the pore pressure profile and of an explicit preconsolidation profile in
+    /// between, from the in-situ effective stress
+    fn layer_profile(&self, f: impl Fn(&SoilLayer, f64, f64) -> f64) -> DepthProfile {
+        let total_stress = self.total_stress_profile();
+        let u = self.pore_pressure_depths();
+
+        DepthProfile::new(
+            self.layer_bounds()
+                .iter()
+                .flat_map(|&(top, bottom, layer)| {
+                    let mut inner = u.clone();
+                    if let Some(Preconsolidation::Profile(pc)) = layer.soil_model.preconsolidation()
+                    {
+                        inner.extend(pc.xs());
+                    }
+                    inner.retain(|&z| z > top && z < bottom);
+                    inner.sort_by(f64::total_cmp);
+                    inner.dedup();
+
+                    std::iter::once(top)
+                        .chain(inner)
+                        .chain(std::iter::once(bottom))
+                        .map(|z| {
+                            let u = self.pore_pressure_profile.eval(z);
+                            let p0 = total_stress.eval(z) - self.suction.effective_pore_pressure(u);
+                            Point::new(z, f(layer, z, p0))
+                        })
+                        .collect::<Vec<Point>>()
+                })
+                .collect(),
+        )
+    }
+
+    /// Unit weight of the soil, constant within each layer
+    #[must_use]
+    pub fn unit_weight_profile(&self) -> DepthProfile {
+        let bounds = self.layer_bounds();
+        let bottom = bounds
+            .last()
+            .map(|&(_, bottom, layer)| Point::new(bottom, layer.soil_model.unit_weight()));
+
+        DepthProfile::new(
+            bounds
+                .iter()
+                .map(|&(top, _, layer)| Point::new(top, layer.soil_model.unit_weight()))
+                .chain(bottom)
+                .collect(),
+        )
+        .with_interpolation(Interpolation::Step)
+        .with_name("Unit weight")
+        .with_unit("kN/m3")
+    }
+
+    /// In-situ total vertical stress, the integral of the unit weight profile
+    #[must_use]
+    pub fn total_stress_profile(&self) -> DepthProfile {
+        self.unit_weight_profile()
+            .cumulative_integral()
+            .with_name("Total stress")
+            .with_unit("kPa")
+    }
+
+    /// In-situ effective vertical stress down to bedrock
+    #[must_use]
+    pub fn effective_stress_profile(&self) -> DepthProfile {
+        let bedrock = self.depth_to_bedrock();
+        let zeros = self
+            .pore_pressure_profile
+            .zeros()
+            .iter()
+            .map(|&z| Point::new(z, 0.0))
+            .collect::<Vec<Point>>();
+        let pore_pressure = if zeros.is_empty() {
+            self.pore_pressure_profile.clone()
+        } else {
+            self.pore_pressure_profile
+                .zip_with(&ProfilePorePressure::new(zeros), |u, _| u)
+        };
+        let effective_stress = self
+            .total_stress_profile()
+            .zip_with(&pore_pressure, |sigma, u| {
+                sigma - self.suction.effective_pore_pressure(u)
+            });
+
+        DepthProfile::new(
+            effective_stress
+                .points()
+                .iter()
+                .filter(|p| p.x >= 0.0 && p.x <= bedrock)
+                .copied()
+                .collect(),
+        )
+        .with_name("Effective stress")
+        .with_unit("kPa")
+    }
+
+    /// Preconsolidation stress, see [`SoilProfile::pc`]
+    #[must_use]
+    pub fn preconsolidation_profile(&self) -> DepthProfile {
+        self.layer_profile(SoilLayer::preconsolidation_stress)
+            .with_name("Preconsolidation stress")
+            .with_unit("kPa")
+    }
+
+    /// Tangent oedometer modulus at the in-situ effective stress
+    #[must_use]
+    pub fn stiffness_profile(&self) -> DepthProfile {
+        self.layer_profile(|layer, z, p0| {
+            let pc = layer.preconsolidation_stress(z, p0);
+            layer.soil_model.elastic_modulus_with_pc(p0, pc, 0.0)
+        })
+        .with_name("Oedometer modulus")
+        .with_unit("kPa")
+    }
+}
+impl SoilProfile {
+    #[must_use]
+    pub fn bedrock_elevation(&self) -> f64 {
+        self.datum.elevation(self.depth_to_bedrock())
+    }
+    #[must_use]
+    pub fn get_soil_layer_at_elevation(&self, elevation: f64) -> Option<&SoilLayer> {
+        self.get_soil_layer(self.datum.depth(elevation))
+    }
+    #[must_use]
+    pub fn in_situ_total_stress_at_elevation(&self, elevation: f64) -> Option<f64> {
+        self.in_situ_total_stress(self.datum.depth(elevation))
+    }
+    #[must_use]
+    pub fn in_situ_effective_stress_at_elevation(&self, elevation: f64) -> Option<f64> {
+        self.in_situ_effective_stress(self.datum.depth(elevation))
     }
 }
@@ -1,12 +1,12 @@
-use crate::hydro::ProfilePorePressure;
-use crate::profile::Point;
+use crate::hydro::{Capillary, ProfilePorePressure, Suction};
+use crate::profile::{Datum, DepthProfile, Point, Profile};
 
 use super::{layer::*, model::*, profile::*};
 use rstest::rstest;
 #[test]
 fn test_elastic_modulus() {
     let soil_layers = Clay {
-        over_consolidation_ratio: 1.2,
+        preconsolidation: Preconsolidation::Ocr(1.2),
         ..Default::default()
     };
 
@@ -139,3 +139,330 @@ fn out_of_range_returns_none(#[case] eval_point: f64) {
 
     assert!(soil_profile.in_situ_total_stress(eval_point).is_none());
 }
+
+fn two_clay_layers() -> SoilProfile {
+    let soil_layers = vec![
+        SoilLayer {
+            thickness: 10.0,
+            soil_model: Box::new(Clay::default()),
+        },
+        SoilLayer {
+            thickness: 10.0,
+            soil_model: Box::new(Clay {
+                unit_weight: 20.0,
+                preconsolidation: Preconsolidation::Ocr(1.5),
+                ..Default::default()
+            }),
+        },
+    ];
+    let pore_pressure_profile =
+        ProfilePorePressure::new(vec![Point::new(5.0, 0.0), Point::new(20.0, 150.0)]);
+
+    SoilProfile::default()
+        .with_soil_layers(soil_layers)
+        .with_pore_pressure_profile(pore_pressure_profile)
+}
+
+#[rstest]
+#[case(0.0, 0.0, 0.0)]
+#[case(5.0, 95.0, 95.0)]
+#[case(10.0, 190.0, 140.0)]
+#[case(15.0, 290.0, 190.0)]
+#[case(20.0, 390.0, 240.0)]
+fn stress_profiles(#[case] depth: f64, #[case] total: f64, #[case] effective: f64) {
+    let soil_profile = two_clay_layers();
+    let total_stress = soil_profile.total_stress_profile();
+    let effective_stress = soil_profile.effective_stress_profile();
+
+    assert_eq!(total_stress.unit(), "kPa");
+    approx::assert_relative_eq!(total_stress.eval(depth), total);
+    approx::assert_relative_eq!(effective_stress.eval(depth), effective);
+    approx::assert_relative_eq!(soil_profile.in_situ_total_stress(depth).unwrap(), total);
+}
+
+#[test]
+fn unit_weight_profile() {
+    let unit_weight = two_clay_layers().unit_weight_profile();
+    approx::assert_relative_eq!(unit_weight.eval(9.9), 19.0);
+    approx::assert_relative_eq!(unit_weight.eval(10.0), 20.0);
+    approx::assert_relative_eq!(unit_weight.eval(20.0), 20.0);
+}
+
+#[test]
+fn preconsolidation_profile() {
+    let pc = two_clay_layers().preconsolidation_profile();
+    approx::assert_relative_eq!(pc.eval(5.0), 95.0);
+    approx::assert_relative_eq!(pc.eval(9.99), 139.91, epsilon = 1e-9);
+    approx::assert_relative_eq!(pc.eval(15.0), 190.0 * 1.5);
+    approx::assert_relative_eq!(pc.eval(20.0), 240.0 * 1.5);
+}
+
+#[test]
+fn preconsolidation_profile_from_explicit_profile() {
+    let pc_profile = DepthProfile::new(vec![
+        Point::new(0.0, 50.0),
+        Point::new(3.0, 300.0),
+        Point::new(20.0, 400.0),
+    ]);
+    let soil_profile = SoilProfile::default()
+        .with_soil_layers(vec![SoilLayer::new(
+            20.0,
+            Box::new(Clay {
+                preconsolidation: Preconsolidation::Profile(pc_profile),
+                ..Default::default()
+            }),
+        )])
+        .with_pore_pressure_profile(ProfilePorePressure::hydrostatic(2.0, 20.0, 10.0));
+    let pc = soil_profile.preconsolidation_profile();
+
+    approx::assert_relative_eq!(pc.eval(3.0), 300.0);
+    for depth in [1.0, 2.0, 3.0, 4.5, 12.0, 20.0] {
+        approx::assert_relative_eq!(
+            pc.eval(depth),
+            soil_profile.pc(depth).unwrap(),
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn stiffness_profile() {
+    let stiffness = two_clay_layers().stiffness_profile();
+    assert_eq!(stiffness.name(), "Oedometer modulus");
+    approx::assert_relative_eq!(stiffness.eval(12.0), Clay::default().M);
+    // Tangent m p0 in the normally consolidated upper layer
+    approx::assert_relative_eq!(stiffness.eval(5.0), Clay::default().m * 95.0);
+}
+
+#[test]
+fn bedrock_within_layer() {
+    let soil_profile = two_clay_layers().with_depth_to_bedrock(15.0);
+    let total_stress = soil_profile.total_stress_profile();
+
+    assert_eq!(total_stress.xs(), vec![0.0, 10.0, 15.0]);
+    approx::assert_relative_eq!(total_stress.eval(15.0), 290.0);
+}
+
+#[rstest]
+#[case(vec![(23.0, 15.0)], SoilProfileError::LayerGap { layer: 0, expected_top: 25.0, top: 23.0 })]
+#[case(vec![(25.0, 15.0), (14.0, 5.0)], SoilProfileError::LayerGap { layer: 1, expected_top: 15.0, top: 14.0 })]
+#[case(vec![(25.0, 25.0)], SoilProfileError::InvalidLayer { layer: 0, top: 25.0, bottom: 25.0 })]
+fn soil_layers_at_elevations_invalid(
+    #[case] bounds: Vec<(f64, f64)>,
+    #[case] expected: SoilProfileError,
+) {
+    let layers = bounds
+        .into_iter()
+        .map(|(top, bottom)| (top, bottom, Box::new(Clay::default()) as Box<dyn SoilModel>))
+        .collect();
+    let result = SoilProfile::default()
+        .with_datum(Datum::new(25.0))
+        .with_soil_layers_at_elevations(layers);
+    assert_eq!(result.unwrap_err(), expected);
+}
+
+#[test]
+fn elevation_queries() {
+    let soil_layers: Vec<(f64, f64, Box<dyn SoilModel>)> = vec![
+        (25.0, 15.0, Box::new(Clay::default())),
+        (
+            15.0,
+            5.0,
+            Box::new(Clay {
+                unit_weight: 20.0,
+                ..Default::default()
+            }),
+        ),
+    ];
+    let soil_profile = SoilProfile::default()
+        .with_datum(Datum::new(25.0))
+        .with_soil_layers_at_elevations(soil_layers)
+        .unwrap()
+        .with_pore_pressure_profile(ProfilePorePressure::new(vec![Point::new(0.0, 0.0)]))
+        .with_bedrock_elevation(10.0);
+
+    approx::assert_relative_eq!(soil_profile.depth_to_bedrock(), 15.0);
+    approx::assert_relative_eq!(soil_profile.bedrock_elevation(), 10.0);
+    approx::assert_relative_eq!(
+        soil_profile
+            .in_situ_total_stress_at_elevation(10.0)
+            .unwrap(),
+        290.0
+    );
+    approx::assert_relative_eq!(
+        soil_profile
+            .get_soil_layer_at_elevation(12.0)
+            .unwrap()
+            .soil_model
+            .unit_weight(),
+        20.0
+    );
+    assert!(soil_profile
+        .in_situ_total_stress_at_elevation(26.0)
+        .is_none());
+}
+
+#[rstest]
+#[case(Suction::Include, 86.0)]
+#[case(Suction::Ignore, 76.0)]
+fn suction_in_effective_stress(#[case] suction: Suction, #[case] expected: f64) {
+    let capillary = Capillary::new(2.0).with_unit_weight_water(10.0);
+    let soil_profile = two_clay_layers();
+    let pore_pressure = capillary.apply(soil_profile.pore_pressure_profile());
+    let soil_profile = soil_profile
+        .with_pore_pressure_profile(pore_pressure)
+        .with_suction(suction);
+
+    approx::assert_relative_eq!(
+        soil_profile.in_situ_effective_stress(4.0).unwrap(),
+        expected
+    );
+    approx::assert_relative_eq!(soil_profile.effective_stress_profile().eval(4.0), expected);
+}
+
+#[rstest]
+#[case(Suction::Include)]
+#[case(Suction::Ignore)]
+fn effective_stress_profile_in_capillary_zone(#[case] suction: Suction) {
+    let soil_profile = two_clay_layers()
+        .with_pore_pressure_profile(ProfilePorePressure::new(vec![
+            Point::new(0.0, -20.0),
+            Point::new(10.0, 80.0),
+        ]))
+        .with_suction(suction);
+    let effective_stress = soil_profile.effective_stress_profile();
+    let stiffness = soil_profile.stiffness_profile();
+
+    for depth in [0.5, 1.0, 1.5, 2.0, 3.0] {
+        let p0 = soil_profile.in_situ_effective_stress(depth).unwrap();
+        approx::assert_relative_eq!(effective_stress.eval(depth), p0, epsilon = 1e-9);
+        approx::assert_relative_eq!(
+            stiffness.eval(depth),
+            Clay::default().m * p0,
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn settlement_below_reference_stress() {
+    let soil_profile = SoilProfile::default()
+        .with_soil_layers(vec![SoilLayer::new(
+            10.0,
+            Box::new(General {
+                a: 0.0,
+                p_ref: 20.0,
+                ..Default::default()
+            }),
+        )])
+        .with_pore_pressure_profile(ProfilePorePressure::hydrostatic(0.0, 10.0, 10.0));
+    let drawdown = soil_profile
+        .pore_pressure_profile()
+        .apply_drawdown(&crate::hydro::Drawdown::new(-50.0));
+    let settlement = soil_profile.compute_settlement(&drawdown);
+
+    assert!(settlement.is_finite() && settlement > 0.0);
+}
+
+#[test]
+fn suction_in_settlement() {
+    // Lowering the water table from 5 m to 8 m only turns pore pressure above 8 m into suction
+    let capillary = Capillary::new(3.0).with_unit_weight_water(10.0);
+    let initial = ProfilePorePressure::hydrostatic(5.0, 20.0, 10.0);
+    let lowered = capillary.apply(&ProfilePorePressure::hydrostatic(8.0, 20.0, 10.0));
+    let settlement = |suction| {
+        two_clay_layers()
+            .with_pore_pressure_profile(initial.clone())
+            .with_suction(suction)
+            .compute_settlement(&lowered)
+    };
+
+    assert!(settlement(Suction::Include) > settlement(Suction::Ignore));
+    assert!(settlement(Suction::Ignore) > 0.0);
+}
+
+#[rstest]
+#[case(Preconsolidation::Ocr(2.0), 190.0)]
+#[case(Preconsolidation::Pop(50.0), 145.0)]
+#[case(Preconsolidation::Profile(DepthProfile::new(vec![Point::new(0.0, 100.0), Point::new(20.0, 300.0)])), 150.0)]
+fn soil_profile_pc(#[case] preconsolidation: Preconsolidation, #[case] expected: f64) {
+    let soil_profile = SoilProfile::default()
+        .with_soil_layers(vec![SoilLayer::new(
+            20.0,
+            Box::new(Clay {
+                preconsolidation,
+                ..Default::default()
+            }),
+        )])
+        .with_pore_pressure_profile(ProfilePorePressure::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(20.0, 0.0),
+        ]));
+
+    approx::assert_relative_eq!(soil_profile.pc(5.0).unwrap(), expected);
+    approx::assert_relative_eq!(soil_profile.preconsolidation_profile().eval(5.0), expected);
+    assert!(soil_profile.pc(25.0).is_none());
+}
+
+#[test]
+fn settlement_uses_in_situ_preconsolidation() {
+    // Lowering the pore pressure by less than the POP stays within the overconsolidated range,
+    // with the drawdown limited by the pore pressure in the top 2 m
+    let soil_profile = |preconsolidation| {
+        SoilProfile::default()
+            .with_soil_layers(vec![SoilLayer::new(
+                10.0,
+                Box::new(Clay {
+                    preconsolidation,
+                    ..Default::default()
+                }),
+            )])
+            .with_pore_pressure_profile(ProfilePorePressure::hydrostatic(0.0, 10.0, 10.0))
+    };
+    let drawdown = ProfilePorePressure::hydrostatic(0.0, 10.0, 10.0).apply_drawdown(
+        &crate::hydro::Drawdown::new(-20.0).with_shape(crate::hydro::DrawdownShape::Uniform),
+    );
+
+    let overconsolidated = soil_profile(Preconsolidation::Pop(30.0)).compute_settlement(&drawdown);
+    approx::assert_relative_eq!(
+        overconsolidated,
+        (20.0 + 20.0 * 8.0) / 5000.0,
+        max_relative = 2e-2
+    );
+    assert!(
+        soil_profile(Preconsolidation::Pop(10.0)).compute_settlement(&drawdown) > overconsolidated
+    );
+}
+
+#[test]
+fn creep_settlement() {
+    let soil_profile = SoilProfile::default()
+        .with_soil_layers(vec![
+            SoilLayer::new(
+                10.0,
+                Box::new(Clay {
+                    creep: Some(Creep::TimeResistance(200.0)),
+                    ..Default::default()
+                }),
+            ),
+            SoilLayer::new(10.0, Box::new(Clay::default())),
+        ])
+        .with_pore_pressure_profile(ProfilePorePressure::hydrostatic(0.0, 20.0, 10.0));
+    let drawdown = ProfilePorePressure::hydrostatic(0.0, 20.0, 10.0).apply_drawdown(
+        &crate::hydro::Drawdown::new(-20.0).with_shape(crate::hydro::DrawdownShape::Uniform),
+    );
+    let year = 365.25 * 86_400.0;
+
+    // Only the top layer creeps, over a decade of time after the reference
+    approx::assert_relative_eq!(
+        soil_profile.creep_settlement(&drawdown, year, 10.0 * year),
+        10.0 * 10.0_f64.ln() / 200.0,
+        max_relative = 2e-2
+    );
+    approx::assert_relative_eq!(soil_profile.creep_settlement(&drawdown, year, year), 0.0);
+    approx::assert_relative_eq!(
+        soil_profile.compute_settlement_with_creep(&drawdown, year, 10.0 * year),
+        soil_profile.compute_settlement(&drawdown)
+            + soil_profile.creep_settlement(&drawdown, year, 10.0 * year)
+    );
+}